@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
+use crate::utils::unquote;
 use crate::Error;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -41,20 +42,8 @@ impl FromStr for AttributePairs {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut result = Self::new();
 
-        for line in split(input, ',') {
-            let pair = split(line.trim(), '=');
-
-            if pair.len() < 2 {
-                continue;
-            }
-
-            let key = pair[0].trim().to_uppercase();
-            let value = pair[1].trim().to_string();
-            if value.is_empty() {
-                continue;
-            }
-
-            result.insert(key.trim().to_string(), value.trim().to_string());
+        for (key, value) in pairs(input) {
+            result.insert(key, value);
         }
 
         #[cfg(test)] // this is very useful, when a test fails!
@@ -63,6 +52,75 @@ impl FromStr for AttributePairs {
     }
 }
 
+/// Parses the attribute list of a tag into its key-value pairs.
+///
+/// The keys are returned in the order, in which they appear in the line, and
+/// the values are unquoted. The line can either be a complete tag (like
+/// `#EXT-X-MEDIA:TYPE=AUDIO`) or only the attribute list.
+///
+/// # Note
+/// This is a low-level function, that is meant to help diagnosing parse
+/// errors. The attribute list is split like the tags split it, but the order
+/// and unknown attributes are kept.
+///
+/// # Example
+/// ```
+/// use hls_m3u8::parse_attributes;
+///
+/// assert_eq!(
+///     parse_attributes("#EXT-X-MEDIA:TYPE=AUDIO,NAME=\"English, US\"")?,
+///     vec![
+///         ("TYPE".to_string(), "AUDIO".to_string()),
+///         ("NAME".to_string(), "English, US".to_string()),
+///     ]
+/// );
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+///
+/// # Errors
+/// This function fails, if the line starts with a `#`, but does not contain
+/// a `:`, which separates the tag from its attribute list.
+pub fn parse_attributes(line: &str) -> crate::Result<Vec<(String, String)>> {
+    let line = line.trim();
+
+    let input = {
+        if line.starts_with('#') {
+            let index = line
+                .find(':')
+                .ok_or_else(|| Error::custom(format!("The tag has no attributes: {:?}", line)))?;
+
+            &line[index + 1..]
+        } else {
+            line
+        }
+    };
+
+    Ok(pairs(input)
+        .map(|(key, value)| (key, unquote(value)))
+        .collect())
+}
+
+/// Splits an attribute list into its key-value pairs in the order, in which
+/// they appear. The keys are uppercased and the values are not unquoted.
+/// Pairs without a value are skipped.
+fn pairs(input: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    split(input, ',').into_iter().filter_map(|pair| {
+        let pair = split(pair.trim(), '=');
+
+        if pair.len() < 2 {
+            return None;
+        }
+
+        let key = pair[0].trim().to_uppercase();
+        let value = pair[1].trim().to_string();
+        if value.is_empty() {
+            return None;
+        }
+
+        Some((key, value))
+    })
+}
+
 fn split(value: &str, terminator: char) -> Vec<String> {
     let mut result = vec![];
 
@@ -145,4 +203,32 @@ mod test {
             map.into_iter().collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn test_parse_attributes() {
+        assert_eq!(
+            parse_attributes(
+                "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",\
+                 DEFAULT=YES,URI=\"eng/prog_index.m3u8\""
+            )
+            .unwrap(),
+            vec![
+                ("TYPE".to_string(), "AUDIO".to_string()),
+                ("GROUP-ID".to_string(), "aac".to_string()),
+                ("NAME".to_string(), "English".to_string()),
+                ("DEFAULT".to_string(), "YES".to_string()),
+                ("URI".to_string(), "eng/prog_index.m3u8".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            parse_attributes("CODECS=\"avc1.4d401f,mp4a.40.2\",BANDWIDTH=1000").unwrap(),
+            vec![
+                ("CODECS".to_string(), "avc1.4d401f,mp4a.40.2".to_string()),
+                ("BANDWIDTH".to_string(), "1000".to_string()),
+            ]
+        );
+
+        assert!(parse_attributes("#EXT-X-ENDLIST").is_err());
+    }
 }
@@ -215,3 +215,7 @@ impl From<::hex::FromHexError> for Error {
         Self::custom(value) // TODO!
     }
 }
+
+impl From<::base64::DecodeError> for Error {
+    fn from(value: ::base64::DecodeError) -> Self { Self::custom(value) }
+}
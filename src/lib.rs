@@ -36,10 +36,14 @@
 //! assert!(m3u8.parse::<MediaPlaylist>().is_ok());
 //! ```
 
+pub use attribute::parse_attributes;
 pub use error::Error;
+pub use live_playlist::LivePlaylistBuilder;
 pub use master_playlist::MasterPlaylist;
-pub use media_playlist::MediaPlaylist;
-pub use media_segment::MediaSegment;
+pub use media_playlist::{MediaPlaylist, ProgressParser};
+pub use media_segment::{MediaSegment, MediaSegmentBuilder, SemanticKey};
+pub use playlist_kind::{playlist_kind, PlaylistKind};
+pub use write_options::{AttributeOrder, WriteOptions};
 
 pub mod tags;
 pub mod types;
@@ -49,10 +53,13 @@ mod utils;
 mod attribute;
 mod error;
 mod line;
+mod live_playlist;
 mod master_playlist;
 mod media_playlist;
 mod media_segment;
+mod playlist_kind;
 mod traits;
+mod write_options;
 
 pub use error::Result;
 pub use traits::*;
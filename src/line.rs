@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
 use crate::tags;
+use crate::utils::tag;
 use crate::Error;
 
 #[derive(Debug, Default)]
@@ -92,6 +93,7 @@ pub enum Tag {
     ExtXVersion(tags::ExtXVersion),
     ExtInf(tags::ExtInf),
     ExtXByteRange(tags::ExtXByteRange),
+    ExtXBitrate(tags::ExtXBitrate),
     ExtXDiscontinuity(tags::ExtXDiscontinuity),
     ExtXKey(tags::ExtXKey),
     ExtXMap(tags::ExtXMap),
@@ -108,8 +110,14 @@ pub enum Tag {
     ExtXIFrameStreamInf(tags::ExtXIFrameStreamInf),
     ExtXSessionData(tags::ExtXSessionData),
     ExtXSessionKey(tags::ExtXSessionKey),
+    ExtXContentSteering(tags::ExtXContentSteering),
     ExtXIndependentSegments(tags::ExtXIndependentSegments),
     ExtXStart(tags::ExtXStart),
+    /// An `EXT-X-VERSION` tag, that declares a version newer than
+    /// [`ProtocolVersion::MAX_SUPPORTED`].
+    ///
+    /// [`ProtocolVersion::MAX_SUPPORTED`]: crate::types::ProtocolVersion::MAX_SUPPORTED
+    UnsupportedVersion(String),
     Unknown(String),
 }
 
@@ -120,6 +128,7 @@ impl fmt::Display for Tag {
             Self::ExtXVersion(value) => value.fmt(f),
             Self::ExtInf(value) => value.fmt(f),
             Self::ExtXByteRange(value) => value.fmt(f),
+            Self::ExtXBitrate(value) => value.fmt(f),
             Self::ExtXDiscontinuity(value) => value.fmt(f),
             Self::ExtXKey(value) => value.fmt(f),
             Self::ExtXMap(value) => value.fmt(f),
@@ -136,8 +145,12 @@ impl fmt::Display for Tag {
             Self::ExtXIFrameStreamInf(value) => value.fmt(f),
             Self::ExtXSessionData(value) => value.fmt(f),
             Self::ExtXSessionKey(value) => value.fmt(f),
+            Self::ExtXContentSteering(value) => value.fmt(f),
             Self::ExtXIndependentSegments(value) => value.fmt(f),
             Self::ExtXStart(value) => value.fmt(f),
+            Self::UnsupportedVersion(value) => {
+                write!(f, "{}{}", tags::ExtXVersion::PREFIX, value)
+            }
             Self::Unknown(value) => value.fmt(f),
         }
     }
@@ -150,11 +163,29 @@ impl FromStr for Tag {
         if input.starts_with(tags::ExtM3u::PREFIX) {
             input.parse().map(Self::ExtM3u)
         } else if input.starts_with(tags::ExtXVersion::PREFIX) {
-            input.parse().map(Self::ExtXVersion)
+            match input.parse() {
+                Ok(version) => Ok(Self::ExtXVersion(version)),
+                Err(e) => {
+                    let version = tag(input, tags::ExtXVersion::PREFIX)?.trim();
+
+                    // every positive integer, that is not a known version, is
+                    // newer than the supported versions.
+                    match version.parse::<u64>() {
+                        Ok(value) if value > 0 => Ok(Self::UnsupportedVersion(version.to_string())),
+                        _ => Err(e),
+                    }
+                }
+            }
         } else if input.starts_with(tags::ExtInf::PREFIX) {
             input.parse().map(Self::ExtInf)
         } else if input.starts_with(tags::ExtXByteRange::PREFIX) {
             input.parse().map(Self::ExtXByteRange)
+        } else if input.starts_with(tags::ExtXBitrate::PREFIX) {
+            input.parse().map(Self::ExtXBitrate)
+        } else if input.starts_with(tags::ExtXDiscontinuitySequence::PREFIX) {
+            // has to be checked before `ExtXDiscontinuity`, because it shares
+            // the same prefix:
+            input.parse().map(Self::ExtXDiscontinuitySequence)
         } else if input.starts_with(tags::ExtXDiscontinuity::PREFIX) {
             input.parse().map(Self::ExtXDiscontinuity)
         } else if input.starts_with(tags::ExtXKey::PREFIX) {
@@ -169,8 +200,6 @@ impl FromStr for Tag {
             input.parse().map(Self::ExtXDateRange)
         } else if input.starts_with(tags::ExtXMediaSequence::PREFIX) {
             input.parse().map(Self::ExtXMediaSequence)
-        } else if input.starts_with(tags::ExtXDiscontinuitySequence::PREFIX) {
-            input.parse().map(Self::ExtXDiscontinuitySequence)
         } else if input.starts_with(tags::ExtXEndList::PREFIX) {
             input.parse().map(Self::ExtXEndList)
         } else if input.starts_with(tags::ExtXPlaylistType::PREFIX) {
@@ -187,6 +216,8 @@ impl FromStr for Tag {
             input.parse().map(Self::ExtXSessionData)
         } else if input.starts_with(tags::ExtXSessionKey::PREFIX) {
             input.parse().map(Self::ExtXSessionKey)
+        } else if input.starts_with(tags::ExtXContentSteering::PREFIX) {
+            input.parse().map(Self::ExtXContentSteering)
         } else if input.starts_with(tags::ExtXIndependentSegments::PREFIX) {
             input.parse().map(Self::ExtXIndependentSegments)
         } else if input.starts_with(tags::ExtXStart::PREFIX) {
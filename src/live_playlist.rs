@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::tags::{ExtXDiscontinuitySequence, ExtXMediaSequence, ExtXTargetDuration};
+use crate::utils::round_duration;
+use crate::{Error, MediaPlaylist, MediaSegment};
+
+/// Generates a live [`MediaPlaylist`] from a sliding window of
+/// [`MediaSegment`]s.
+///
+/// Every time a segment is pushed, that does not fit into the window, the
+/// oldest segment is removed and the [`ExtXMediaSequence`] (and the
+/// [`ExtXDiscontinuitySequence`], if the removed segment started a
+/// discontinuity) are advanced accordingly.
+///
+/// The [`ExtXTargetDuration`] is the largest rounded segment duration, that
+/// has been pushed so far, because it must not change during the lifetime of
+/// a live playlist (see [6.2.1. General Server Responsibilities]).
+///
+/// # Example
+/// ```
+/// # use hls_m3u8::{LivePlaylistBuilder, MediaSegment};
+/// use hls_m3u8::tags::ExtInf;
+/// use std::time::Duration;
+///
+/// let mut live = LivePlaylistBuilder::new(2);
+///
+/// for uri in &["first.ts", "second.ts", "third.ts"] {
+///     live.push_segment(
+///         MediaSegment::builder()
+///             .inf_tag(ExtInf::new(Duration::from_secs(6)))
+///             .uri(*uri)
+///             .build()
+///             .unwrap(),
+///     );
+/// }
+///
+/// let playlist = live.snapshot()?;
+///
+/// assert_eq!(playlist.segments().len(), 2);
+/// assert_eq!(playlist.segments()[0].uri(), "second.ts");
+/// assert_eq!(playlist.media_sequence_tag().unwrap().seq_num(), 1);
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+///
+/// [6.2.1. General Server Responsibilities]:
+/// https://tools.ietf.org/html/rfc8216#section-6.2.1
+#[derive(Debug, Clone, PartialEq)]
+pub struct LivePlaylistBuilder {
+    window: usize,
+    segments: VecDeque<MediaSegment>,
+    media_sequence: u64,
+    discontinuity_sequence: u64,
+    target_duration: Duration,
+}
+
+impl LivePlaylistBuilder {
+    /// Makes a new [`LivePlaylistBuilder`], that keeps at most `window`
+    /// segments in the playlist.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::LivePlaylistBuilder;
+    /// let live = LivePlaylistBuilder::new(6);
+    /// ```
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            segments: VecDeque::with_capacity(window),
+            media_sequence: 0,
+            discontinuity_sequence: 0,
+            target_duration: Duration::from_secs(0),
+        }
+    }
+
+    /// Adds a segment to the end of the window and removes the oldest
+    /// segments, if the window is full.
+    pub fn push_segment(&mut self, segment: MediaSegment) -> &mut Self {
+        let rounded_duration = round_duration(segment.inf_tag().duration());
+
+        if rounded_duration > self.target_duration {
+            self.target_duration = rounded_duration;
+        }
+
+        self.segments.push_back(segment);
+
+        while self.segments.len() > self.window {
+            if let Some(segment) = self.segments.pop_front() {
+                self.media_sequence += 1;
+
+                if segment.discontinuity_tag().is_some() {
+                    self.discontinuity_sequence += 1;
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Returns the media sequence number of the first segment in the window.
+    pub const fn media_sequence(&self) -> u64 { self.media_sequence }
+
+    /// Returns the discontinuity sequence number of the first segment in the
+    /// window.
+    pub const fn discontinuity_sequence(&self) -> u64 { self.discontinuity_sequence }
+
+    /// Returns the segments, that are currently in the window.
+    pub const fn segments(&self) -> &VecDeque<MediaSegment> { &self.segments }
+
+    /// Makes a [`MediaPlaylist`] of the current window.
+    ///
+    /// # Errors
+    /// This function fails, if the segments do not form a valid
+    /// [`MediaPlaylist`] (for example because of an invalid byte range).
+    pub fn snapshot(&self) -> crate::Result<MediaPlaylist> {
+        let mut builder = MediaPlaylist::builder();
+
+        builder
+            .target_duration_tag(ExtXTargetDuration::new(self.target_duration))
+            .media_sequence_tag(ExtXMediaSequence::new(self.media_sequence))
+            .segments(self.segments.iter().cloned().collect::<Vec<_>>());
+
+        if self.discontinuity_sequence != 0 {
+            builder.discontinuity_sequence_tag(ExtXDiscontinuitySequence::new(
+                self.discontinuity_sequence,
+            ));
+        }
+
+        builder.build().map_err(Error::builder_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::{ExtInf, ExtXDiscontinuity};
+    use pretty_assertions::assert_eq;
+
+    fn segment(uri: &str, duration: f64) -> MediaSegment {
+        MediaSegment::builder()
+            .inf_tag(ExtInf::new(Duration::from_secs_f64(duration)))
+            .uri(uri)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut live = LivePlaylistBuilder::new(3);
+
+        // first tick:
+        live.push_segment(segment("0.ts", 6.0))
+            .push_segment(segment("1.ts", 6.0));
+
+        assert_eq!(
+            live.snapshot().unwrap().to_string(),
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:6\n\
+             #EXT-X-MEDIA-SEQUENCE:0\n\
+             #EXTINF:6,\n\
+             0.ts\n\
+             #EXTINF:6,\n\
+             1.ts\n"
+                .to_string()
+        );
+
+        // second tick:
+        let mut discontinuity = segment("2.ts", 5.5);
+        discontinuity.set_discontinuity_tag(Some(ExtXDiscontinuity));
+
+        live.push_segment(discontinuity)
+            .push_segment(segment("3.ts", 6.0));
+
+        assert_eq!(live.media_sequence(), 1);
+        assert_eq!(live.discontinuity_sequence(), 0);
+        assert_eq!(
+            live.snapshot().unwrap().to_string(),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:3\n\
+             #EXT-X-TARGETDURATION:6\n\
+             #EXT-X-MEDIA-SEQUENCE:1\n\
+             #EXTINF:6,\n\
+             1.ts\n\
+             #EXT-X-DISCONTINUITY\n\
+             #EXTINF:5.5,\n\
+             2.ts\n\
+             #EXTINF:6,\n\
+             3.ts\n"
+                .to_string()
+        );
+
+        // third tick:
+        live.push_segment(segment("4.ts", 7.0))
+            .push_segment(segment("5.ts", 6.0));
+
+        let playlist = live.snapshot().unwrap();
+
+        assert_eq!(live.media_sequence(), 3);
+        assert_eq!(live.discontinuity_sequence(), 1);
+        assert_eq!(
+            playlist.target_duration_tag(),
+            ExtXTargetDuration::new(Duration::from_secs(7))
+        );
+        assert_eq!(
+            playlist.discontinuity_sequence_tag(),
+            Some(ExtXDiscontinuitySequence::new(1))
+        );
+        assert_eq!(
+            playlist
+                .segments()
+                .iter()
+                .map(|s| s.uri().as_str())
+                .collect::<Vec<_>>(),
+            vec!["3.ts", "4.ts", "5.ts"]
+        );
+    }
+}
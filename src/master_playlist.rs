@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt;
 use std::str::FromStr;
 
@@ -6,17 +6,36 @@ use derive_builder::Builder;
 
 use crate::line::{Line, Lines, Tag};
 use crate::tags::{
-    ExtM3u, ExtXIFrameStreamInf, ExtXIndependentSegments, ExtXMedia, ExtXSessionData,
-    ExtXSessionKey, ExtXStart, ExtXStreamInf, ExtXVersion,
+    ExtM3u, ExtXContentSteering, ExtXIFrameStreamInf, ExtXIndependentSegments, ExtXMedia,
+    ExtXSessionData, ExtXSessionKey, ExtXStart, ExtXStreamInf, ExtXVersion,
 };
-use crate::types::{ClosedCaptions, MediaType, ProtocolVersion};
-use crate::{Error, RequiredVersion};
+use crate::types::{ClosedCaptions, HdcpLevel, MediaType, ProtocolVersion};
+use crate::{Error, RequiredVersion, WriteOptions};
 
-#[derive(Debug, Clone, Builder, PartialEq)]
-#[builder(build_fn(validate = "Self::validate"))]
-#[builder(setter(into, strip_option))]
+#[derive(Debug, Clone)]
 /// Master playlist.
 pub struct MasterPlaylist {
+    independent_segments_tag: Option<ExtXIndependentSegments>,
+    start_tag: Option<ExtXStart>,
+    media_tags: Vec<ExtXMedia>,
+    stream_inf_tags: Vec<ExtXStreamInf>,
+    i_frame_stream_inf_tags: Vec<ExtXIFrameStreamInf>,
+    session_data_tags: Vec<ExtXSessionData>,
+    session_key_tags: Vec<ExtXSessionKey>,
+    content_steering_tag: Option<ExtXContentSteering>,
+    document_order: Vec<MasterTag>,
+    unknown_tags: Vec<String>,
+}
+
+/// The fields of a [`MasterPlaylist`] and the options of its builder, which
+/// are not part of the playlist itself.
+// The options are only read from the builder, so they are never read here.
+#[allow(dead_code)]
+#[derive(Builder)]
+#[builder(name = "MasterPlaylistBuilder", public)]
+#[builder(build_fn(validate = "Self::validate", name = "build_fields", private))]
+#[builder(setter(into, strip_option))]
+struct MasterPlaylistFields {
     #[builder(default)]
     /// Sets the [`ExtXIndependentSegments`] tag.
     ///
@@ -59,6 +78,40 @@ pub struct MasterPlaylist {
     /// # Note
     /// This tag is optional.
     session_key_tags: Vec<ExtXSessionKey>,
+    #[builder(default)]
+    /// Sets the [`ExtXContentSteering`] tag.
+    ///
+    /// # Note
+    /// This tag is optional.
+    content_steering_tag: Option<ExtXContentSteering>,
+    /// Rejects playlists, that declare a version newer than
+    /// [`ProtocolVersion::MAX_SUPPORTED`] in their [`ExtXVersion`] tag.
+    ///
+    /// By default such playlists are parsed leniently and the declared
+    /// version is ignored, like every [`ExtXVersion`] tag.
+    ///
+    /// # Error
+    /// If this is enabled, parsing a playlist with
+    /// [`MasterPlaylistBuilder::parse`] will fail with an `Unknown Protocol
+    /// version` error, if it declares an unsupported version.
+    ///
+    /// The default value is `false`.
+    #[builder(default)]
+    reject_unsupported_version: bool,
+}
+
+/// The tags of a [`MasterPlaylist`], whose position in the document can be
+/// preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MasterTag {
+    Media,
+    StreamInf,
+    IFrameStreamInf,
+    SessionData,
+    SessionKey,
+    ContentSteering,
+    IndependentSegments,
+    Start,
 }
 
 impl MasterPlaylist {
@@ -107,6 +160,39 @@ impl MasterPlaylist {
     /// Returns the [`ExtXMedia`] tags contained in the playlist.
     pub const fn media_tags(&self) -> &Vec<ExtXMedia> { &self.media_tags }
 
+    /// Returns the [`ExtXMedia`] tags contained in the playlist in the order,
+    /// they should be displayed to a user (see
+    /// [`ExtXMedia::display_sort_key`]).
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"French\",URI=\"fr.m3u8\"\n\
+    ///                 #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,\
+    ///                 AUTOSELECT=YES,URI=\"en.m3u8\"\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+    ///                 http://example.com/low.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let names = playlist
+    ///     .sorted_renditions()
+    ///     .iter()
+    ///     .map(|m| m.name().as_str())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(names, vec!["English", "French"]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXMedia::display_sort_key`]:
+    /// crate::tags::ExtXMedia::display_sort_key
+    pub fn sorted_renditions(&self) -> Vec<&ExtXMedia> {
+        let mut result = self.media_tags.iter().collect::<Vec<_>>();
+        result.sort_by_cached_key(|m| m.display_sort_key());
+        result
+    }
+
     /// Appends an [`ExtXMedia`].
     pub fn push_media_tag(&mut self, value: ExtXMedia) -> &mut Self {
         self.media_tags.push(value);
@@ -140,6 +226,181 @@ impl MasterPlaylist {
         self
     }
 
+    /// Returns the identifiers of all content steering pathways, that are
+    /// referenced by the [`ExtXStreamInf`] tags, in the order in which they
+    /// first appear.
+    ///
+    /// # Note
+    /// A variant stream without a `PATHWAY-ID` attribute belongs to the
+    /// pathway `"."`.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000,PATHWAY-ID=\"CDN-A\"\n\
+    ///                 http://a.example.com/low/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.pathways(), vec!["CDN-A", "."]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn pathways(&self) -> Vec<&str> {
+        let mut result = vec![];
+
+        for pathway_id in self.stream_inf_tags.iter().map(pathway_id_of) {
+            if !result.contains(&pathway_id) {
+                result.push(pathway_id);
+            }
+        }
+
+        result
+    }
+
+    /// Returns all [`ExtXStreamInf`] tags, that belong to the content steering
+    /// pathway with the given identifier.
+    ///
+    /// # Note
+    /// A variant stream without a `PATHWAY-ID` attribute belongs to the
+    /// pathway `"."`.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000,PATHWAY-ID=\"CDN-A\"\n\
+    ///                 http://a.example.com/low/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let variants = playlist.variants_for_pathway("CDN-A");
+    ///
+    /// assert_eq!(variants.len(), 1);
+    /// assert_eq!(variants[0].uri(), "http://a.example.com/low/index.m3u8");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn variants_for_pathway(&self, pathway_id: &str) -> Vec<&ExtXStreamInf> {
+        self.stream_inf_tags
+            .iter()
+            .filter(|t| pathway_id_of(t) == pathway_id)
+            .collect()
+    }
+
+    /// Returns each pair of consecutive bandwidths in the bitrate ladder of
+    /// the [`ExtXStreamInf`] tags together with the ratio of the higher to
+    /// the lower bandwidth.
+    ///
+    /// The bandwidths are sorted in ascending order and duplicates are
+    /// removed. A ratio above `2.0` usually indicates a gap in the ladder.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=2000000\n\
+    ///                 http://example.com/mid/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1000000\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.bandwidth_gaps(), vec![(1_000_000, 2_000_000, 2.0)]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn bandwidth_gaps(&self) -> Vec<(u64, u64, f64)> {
+        let bandwidths = self
+            .stream_inf_tags
+            .iter()
+            .map(|t| t.bandwidth())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        bandwidths
+            .windows(2)
+            .map(|w| (w[0], w[1], w[1] as f64 / w[0] as f64))
+            .collect()
+    }
+
+    /// Returns all [`ExtXStreamInf`] tags, that can be played with the given
+    /// throughput (in bits per second), sorted from the highest to the lowest
+    /// bandwidth.
+    ///
+    /// A variant stream can be played, if its `BANDWIDTH` multiplied with the
+    /// `safety_factor` does not exceed the `throughput_bps`. If it has an
+    /// `AVERAGE-BANDWIDTH`, this has to fit into the throughput as well.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1000000\n\
+    ///                 http://example.com/low/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=2000000\n\
+    ///                 http://example.com/mid/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=4000000\n\
+    ///                 http://example.com/high/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let variants = playlist.sustainable_variants(3_000_000, 1.2);
+    ///
+    /// assert_eq!(variants.len(), 2);
+    /// assert_eq!(variants[0].uri(), "http://example.com/mid/index.m3u8");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn sustainable_variants(
+        &self,
+        throughput_bps: u64,
+        safety_factor: f64,
+    ) -> Vec<&ExtXStreamInf> {
+        let fits = |bandwidth: u64| bandwidth as f64 * safety_factor <= throughput_bps as f64;
+
+        let mut result = self
+            .stream_inf_tags
+            .iter()
+            .filter(|t| fits(t.bandwidth()) && t.average_bandwidth().into_iter().all(fits))
+            .collect::<Vec<_>>();
+
+        result.sort_by(|a, b| {
+            (b.bandwidth(), b.average_bandwidth()).cmp(&(a.bandwidth(), a.average_bandwidth()))
+        });
+
+        result
+    }
+
+    /// Returns all [`ExtXStreamInf`] tags, that can be played on a device,
+    /// which supports at most the given [`HdcpLevel`].
+    ///
+    /// A variant stream without an `HDCP-LEVEL` attribute is treated like
+    /// [`HdcpLevel::None`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use hls_m3u8::types::HdcpLevel;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1000000\n\
+    ///                 http://example.com/low/index.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=8000000,HDCP-LEVEL=TYPE-1\n\
+    ///                 http://example.com/uhd/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let variants = playlist.variants_for_hdcp(HdcpLevel::Type0);
+    ///
+    /// assert_eq!(variants.len(), 1);
+    /// assert_eq!(variants[0].uri(), "http://example.com/low/index.m3u8");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn variants_for_hdcp(&self, max_level: HdcpLevel) -> Vec<&ExtXStreamInf> {
+        self.stream_inf_tags
+            .iter()
+            .filter(|t| t.hdcp_level().unwrap_or(HdcpLevel::None) <= max_level)
+            .collect()
+    }
+
     /// Returns the [`ExtXIFrameStreamInf`] tags contained in the playlist.
     pub const fn i_frame_stream_inf_tags(&self) -> &Vec<ExtXIFrameStreamInf> {
         &self.i_frame_stream_inf_tags
@@ -181,6 +442,56 @@ impl MasterPlaylist {
     /// Returns the [`ExtXSessionKey`] tags contained in the playlist.
     pub const fn session_key_tags(&self) -> &Vec<ExtXSessionKey> { &self.session_key_tags }
 
+    /// Returns the [`ExtXSessionKey`] tags, that can be used to preload the
+    /// keys of the [`MediaPlaylist`]s, before they are fetched.
+    ///
+    /// Keys, that are declared more than once, are only returned once. An
+    /// [`ExtXSessionKey`] can be converted into the equivalent [`ExtXKey`]
+    /// with [`From`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use hls_m3u8::tags::ExtXKey;
+    /// use hls_m3u8::types::EncryptionMethod;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-SESSION-KEY:METHOD=AES-128,URI=\"https://www.example.com/key\"\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let keys = playlist
+    ///     .preload_keys()
+    ///     .into_iter()
+    ///     .cloned()
+    ///     .map(ExtXKey::from)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     keys,
+    ///     vec![ExtXKey::new(
+    ///         EncryptionMethod::Aes128,
+    ///         "https://www.example.com/key"
+    ///     )]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    /// [`ExtXKey`]: crate::tags::ExtXKey
+    pub fn preload_keys(&self) -> Vec<&ExtXSessionKey> {
+        let mut result: Vec<&ExtXSessionKey> = vec![];
+
+        for key in &self.session_key_tags {
+            if !result.contains(&key) {
+                result.push(key);
+            }
+        }
+
+        result
+    }
+
     /// Appends an [`ExtXSessionKey`].
     pub fn push_session_key(&mut self, value: ExtXSessionKey) -> &mut Self {
         self.session_key_tags.push(value);
@@ -195,6 +506,192 @@ impl MasterPlaylist {
         self.session_key_tags = value.into_iter().map(Into::into).collect();
         self
     }
+
+    /// Returns the [`ExtXContentSteering`] tag contained in the playlist.
+    ///
+    /// # Note
+    /// The draft, that defines the tag, has no attribute to defer the loading
+    /// of the Content Steering Manifest, so there is no way to tell, whether
+    /// a client should wait before loading it.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-CONTENT-STEERING:SERVER-URI=\"manifest.json\",PATHWAY-ID=\"CDN-A\"\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000,PATHWAY-ID=\"CDN-A\"\n\
+    ///                 http://a.example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let content_steering = playlist.content_steering().as_ref().unwrap();
+    ///
+    /// assert_eq!(content_steering.server_uri(), "manifest.json");
+    /// assert_eq!(content_steering.pathway_id(), &Some("CDN-A".to_string()));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub const fn content_steering(&self) -> &Option<ExtXContentSteering> {
+        &self.content_steering_tag
+    }
+
+    /// Sets the [`ExtXContentSteering`] tag contained in the playlist.
+    pub fn set_content_steering<T>(&mut self, value: Option<T>) -> &mut Self
+    where
+        T: Into<ExtXContentSteering>,
+    {
+        self.content_steering_tag = value.map(Into::into);
+        self
+    }
+
+    /// Returns all tags, that have not been recognized while parsing the
+    /// playlist, in the order in which they appeared.
+    ///
+    /// # Note
+    /// Unknown tags are ignored by [6.3.1. General Client Responsibilities],
+    /// so they are only collected while parsing and not written by the
+    /// [`Display`] implementation.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-NEW-TAG:VALUE=1\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=150000\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.unknown_tags(), &["#EXT-X-NEW-TAG:VALUE=1".to_string()]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [6.3.1. General Client Responsibilities]:
+    /// https://tools.ietf.org/html/rfc8216#section-6.3.1
+    pub fn unknown_tags(&self) -> &[String] { &self.unknown_tags }
+
+    /// Returns a copy of the playlist for clients, that can only play audio.
+    ///
+    /// The copy only contains the audio renditions and the variant streams,
+    /// which are audio-only. A variant stream is audio-only, if it has no
+    /// `RESOLUTION` and no `VIDEO` group and all of its `CODECS` are audio
+    /// codecs. The `SUBTITLES` and `CLOSED-CAPTIONS` groups of the remaining
+    /// variant streams are removed, because the renditions are dropped.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.5\"\n\
+    ///                 http://example.com/audio.m3u8\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\"\n\
+    ///                 http://example.com/low.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let audio_only = playlist.audio_only()?;
+    ///
+    /// assert_eq!(audio_only.stream_inf_tags().len(), 1);
+    /// assert_eq!(
+    ///     audio_only.stream_inf_tags()[0].uri(),
+    ///     "http://example.com/audio.m3u8"
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if the playlist does not contain an audio-only
+    /// variant stream.
+    pub fn audio_only(&self) -> crate::Result<Self> {
+        let stream_inf_tags = self
+            .stream_inf_tags
+            .iter()
+            .filter(|t| is_audio_only(t))
+            .map(|t| {
+                let mut t = t.clone();
+                t.set_subtitles(None::<String>);
+                t.set_closed_captions(None);
+                t
+            })
+            .collect::<Vec<_>>();
+
+        if stream_inf_tags.is_empty() {
+            return Err(Error::custom(
+                "The playlist does not contain an audio-only variant stream",
+            ));
+        }
+
+        let media_tags = self
+            .media_tags
+            .iter()
+            .filter(|t| t.media_type() == MediaType::Audio)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut builder = Self::builder();
+
+        builder
+            .media_tags(media_tags)
+            .stream_inf_tags(stream_inf_tags)
+            .session_data_tags(self.session_data_tags.clone())
+            .session_key_tags(self.session_key_tags.clone());
+
+        if let Some(value) = &self.content_steering_tag {
+            builder.content_steering_tag(value.clone());
+        }
+
+        if let Some(value) = self.independent_segments_tag {
+            builder.independent_segments_tag(value);
+        }
+
+        if let Some(value) = self.start_tag {
+            builder.start_tag(value);
+        }
+
+        let mut result = builder.build().map_err(Error::builder_error)?;
+
+        // the recorded positions of the removed tags are dropped as well:
+        let mut media_tags = self.media_tags.iter();
+        let mut stream_inf_tags = self.stream_inf_tags.iter();
+
+        result.document_order = self
+            .document_order
+            .iter()
+            .copied()
+            .filter(|kind| match kind {
+                MasterTag::Media => media_tags
+                    .next()
+                    .filter(|t| t.media_type() == MediaType::Audio)
+                    .is_some(),
+                MasterTag::StreamInf => stream_inf_tags
+                    .next()
+                    .filter(|t| is_audio_only(t))
+                    .is_some(),
+                MasterTag::IFrameStreamInf => false,
+                _ => true,
+            })
+            .collect();
+        result.unknown_tags = self.unknown_tags.clone();
+
+        Ok(result)
+    }
+}
+
+/// Returns `true`, if the variant stream does not contain any video.
+fn is_audio_only(tag: &ExtXStreamInf) -> bool {
+    const AUDIO_CODECS: &[&str] = &["mp4a", "ac-3", "ec-3", "ac-4", "opus", "flac", "alac"];
+
+    let codecs = tag.codec_list();
+
+    tag.resolution().is_none()
+        && tag.video().is_none()
+        && !codecs.is_empty()
+        && codecs
+            .iter()
+            .all(|c| AUDIO_CODECS.contains(&c.family().to_ascii_lowercase().as_str()))
+}
+
+/// Returns the content steering pathway of a variant stream, which defaults to
+/// `"."`.
+fn pathway_id_of(tag: &ExtXStreamInf) -> &str {
+    tag.pathway_id().as_ref().map_or(".", String::as_str)
 }
 
 impl RequiredVersion for MasterPlaylist {
@@ -206,18 +703,94 @@ impl RequiredVersion for MasterPlaylist {
             self.stream_inf_tags,
             self.i_frame_stream_inf_tags,
             self.session_data_tags,
-            self.session_key_tags
+            self.session_key_tags,
+            self.content_steering_tag
         ]
     }
 }
 
+// The recorded document order only affects, how the playlist is written, so
+// it is not part of the comparison.
+impl PartialEq for MasterPlaylist {
+    fn eq(&self, other: &Self) -> bool {
+        self.independent_segments_tag == other.independent_segments_tag
+            && self.start_tag == other.start_tag
+            && self.media_tags == other.media_tags
+            && self.stream_inf_tags == other.stream_inf_tags
+            && self.i_frame_stream_inf_tags == other.i_frame_stream_inf_tags
+            && self.session_data_tags == other.session_data_tags
+            && self.session_key_tags == other.session_key_tags
+            && self.content_steering_tag == other.content_steering_tag
+            && self.unknown_tags == other.unknown_tags
+    }
+}
+
 impl MasterPlaylistBuilder {
+    /// Builds a new [`MasterPlaylist`].
+    ///
+    /// # Errors
+    /// If a required field has not been initialized or if the playlist is
+    /// invalid.
+    pub fn build(&self) -> Result<MasterPlaylist, String> {
+        let MasterPlaylistFields {
+            independent_segments_tag,
+            start_tag,
+            media_tags,
+            stream_inf_tags,
+            i_frame_stream_inf_tags,
+            session_data_tags,
+            session_key_tags,
+            content_steering_tag,
+            reject_unsupported_version: _,
+        } = self.build_fields()?;
+
+        Ok(MasterPlaylist {
+            independent_segments_tag,
+            start_tag,
+            media_tags,
+            stream_inf_tags,
+            i_frame_stream_inf_tags,
+            session_data_tags,
+            session_key_tags,
+            content_steering_tag,
+            document_order: vec![],
+            unknown_tags: vec![],
+        })
+    }
+
+    /// Parse the rest of the [`MasterPlaylist`] from an m3u8 file.
+    ///
+    /// # Example
+    /// ```
+    /// use hls_m3u8::{MasterPlaylist, WriteOptions};
+    ///
+    /// let input = "#EXTM3U\n\
+    ///              #EXT-X-STREAM-INF:BANDWIDTH=150000\n\
+    ///              http://example.com/low/index.m3u8\n\
+    ///              #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\"\n";
+    ///
+    /// let playlist = MasterPlaylist::builder().parse(input)?;
+    ///
+    /// let options = WriteOptions {
+    ///     preserve_document_order: true,
+    ///     ..WriteOptions::default()
+    /// };
+    ///
+    /// assert_eq!(playlist.to_string_with(&options), input);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn parse(&mut self, input: &str) -> crate::Result<MasterPlaylist> {
+        parse_master_playlist(input, self)
+    }
+
     fn validate(&self) -> Result<(), String> {
         self.validate_stream_inf_tags().map_err(|e| e.to_string())?;
         self.validate_i_frame_stream_inf_tags()
             .map_err(|e| e.to_string())?;
         self.validate_session_data_tags()
             .map_err(|e| e.to_string())?;
+        self.validate_default_renditions()
+            .map_err(|e| e.to_string())?;
 
         Ok(())
     }
@@ -290,6 +863,57 @@ impl MasterPlaylistBuilder {
         Ok(())
     }
 
+    /// Each group, that is referenced by a variant stream, must not contain
+    /// more than one rendition with `DEFAULT=YES`. Groups, that are not
+    /// referenced, are ignored.
+    fn validate_default_renditions(&self) -> crate::Result<()> {
+        let mut groups = BTreeSet::new();
+
+        if let Some(value) = &self.stream_inf_tags {
+            for t in value {
+                if let Some(group_id) = t.audio() {
+                    groups.insert((MediaType::Audio, group_id));
+                }
+                if let Some(group_id) = t.video() {
+                    groups.insert((MediaType::Video, group_id));
+                }
+                if let Some(group_id) = t.subtitles() {
+                    groups.insert((MediaType::Subtitles, group_id));
+                }
+                if let Some(ClosedCaptions::GroupId(group_id)) = t.closed_captions() {
+                    groups.insert((MediaType::ClosedCaptions, group_id));
+                }
+            }
+        }
+        if let Some(value) = &self.i_frame_stream_inf_tags {
+            for t in value {
+                if let Some(group_id) = t.video() {
+                    groups.insert((MediaType::Video, group_id));
+                }
+            }
+        }
+
+        if let Some(value) = &self.media_tags {
+            for (media_type, group_id) in groups {
+                let defaults = value
+                    .iter()
+                    .filter(|t| {
+                        t.is_default() && t.media_type() == media_type && t.group_id() == group_id
+                    })
+                    .map(|t| t.name())
+                    .collect::<Vec<_>>();
+
+                if defaults.len() > 1 {
+                    return Err(Error::custom(format!(
+                        "Multiple default renditions in the {} group {:?}: {:?}",
+                        media_type, group_id, defaults
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn check_media_group<T: ToString>(&self, media_type: MediaType, group_id: T) -> bool {
         if let Some(value) = &self.media_tags {
             value
@@ -314,137 +938,256 @@ impl RequiredVersion for MasterPlaylistBuilder {
             self.stream_inf_tags,
             self.i_frame_stream_inf_tags,
             self.session_data_tags,
-            self.session_key_tags
+            self.session_key_tags,
+            self.content_steering_tag.clone().flatten()
         ]
     }
 }
 
 impl fmt::Display for MasterPlaylist {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_with(f, &WriteOptions::default())
+    }
+}
+
+impl MasterPlaylist {
+    /// Writes the playlist with the given [`WriteOptions`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use hls_m3u8::{AttributeOrder, WriteOptions};
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-MEDIA:TYPE=AUDIO,URI=\"en.m3u8\",GROUP-ID=\"aac\",NAME=\"English\"\n\
+    ///                 #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+    ///                 http://example.com/low/index.m3u8"
+    ///     .parse::<MasterPlaylist>()?;
+    ///
+    /// let options = WriteOptions {
+    ///     media_attribute_order: AttributeOrder::AppleCompatible,
+    ///     ..WriteOptions::default()
+    /// };
+    ///
+    /// assert!(playlist.to_string_with(&options).contains(
+    ///     "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",URI=\"en.m3u8\"\n"
+    /// ));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn to_string_with(&self, options: &WriteOptions) -> String {
+        let mut result = String::new();
+        // writing into a `String` can not fail
+        self.write_with(&mut result, options).unwrap();
+
+        if !options.final_newline && result.ends_with('\n') {
+            result.pop();
+        }
+
+        result
+    }
+
+    fn write_with<W: fmt::Write>(&self, f: &mut W, options: &WriteOptions) -> fmt::Result {
         writeln!(f, "{}", ExtM3u)?;
-        if self.required_version() != ProtocolVersion::V1 {
+        if options.always_emit_version || self.required_version() != ProtocolVersion::V1 {
             writeln!(f, "{}", ExtXVersion::new(self.required_version()))?;
         }
-        for t in &self.media_tags {
+        let order = options.media_attribute_order;
+
+        let mut media_tags = self.media_tags.iter();
+        let mut stream_inf_tags = self.stream_inf_tags.iter();
+        let mut i_frame_stream_inf_tags = self.i_frame_stream_inf_tags.iter();
+        let mut session_data_tags = self.session_data_tags.iter();
+        let mut session_key_tags = self.session_key_tags.iter();
+        let mut content_steering_tag = self.content_steering_tag.iter();
+        let mut independent_segments_tag = self.independent_segments_tag.iter();
+        let mut start_tag = self.start_tag.iter();
+
+        if options.preserve_document_order {
+            for kind in &self.document_order {
+                let value = match kind {
+                    MasterTag::Media => {
+                        if let Some(t) = media_tags.next() {
+                            t.write_with(f, order)?;
+                            writeln!(f)?;
+                        }
+                        continue;
+                    }
+                    MasterTag::StreamInf => stream_inf_tags.next().map(ToString::to_string),
+                    MasterTag::IFrameStreamInf => {
+                        i_frame_stream_inf_tags.next().map(ToString::to_string)
+                    }
+                    MasterTag::SessionData => session_data_tags.next().map(ToString::to_string),
+                    MasterTag::SessionKey => session_key_tags.next().map(ToString::to_string),
+                    MasterTag::ContentSteering => {
+                        content_steering_tag.next().map(ToString::to_string)
+                    }
+                    MasterTag::IndependentSegments => {
+                        independent_segments_tag.next().map(ToString::to_string)
+                    }
+                    MasterTag::Start => start_tag.next().map(ToString::to_string),
+                };
+
+                if let Some(value) = value {
+                    writeln!(f, "{}", value)?;
+                }
+            }
+        }
+
+        // everything, that is not covered by the document order:
+        for t in media_tags {
+            t.write_with(f, order)?;
+            writeln!(f)?;
+        }
+        for t in stream_inf_tags {
             writeln!(f, "{}", t)?;
         }
-        for t in &self.stream_inf_tags {
+        for t in i_frame_stream_inf_tags {
             writeln!(f, "{}", t)?;
         }
-        for t in &self.i_frame_stream_inf_tags {
+        for t in session_data_tags {
             writeln!(f, "{}", t)?;
         }
-        for t in &self.session_data_tags {
+        for t in session_key_tags {
             writeln!(f, "{}", t)?;
         }
-        for t in &self.session_key_tags {
+        for t in content_steering_tag {
             writeln!(f, "{}", t)?;
         }
-        if let Some(value) = &self.independent_segments_tag {
-            writeln!(f, "{}", value)?;
+        for t in independent_segments_tag {
+            writeln!(f, "{}", t)?;
         }
-        if let Some(value) = &self.start_tag {
-            writeln!(f, "{}", value)?;
+        for t in start_tag {
+            writeln!(f, "{}", t)?;
         }
         Ok(())
     }
 }
 
-impl FromStr for MasterPlaylist {
-    type Err = Error;
+fn parse_master_playlist(
+    input: &str,
+    builder: &mut MasterPlaylistBuilder,
+) -> crate::Result<MasterPlaylist> {
+    let mut media_tags = vec![];
+    let mut stream_inf_tags = vec![];
+    let mut i_frame_stream_inf_tags = vec![];
+    let mut session_data_tags = vec![];
+    let mut session_key_tags = vec![];
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut builder = Self::builder();
+    let mut document_order = vec![];
+    let mut unknown_tags = vec![];
 
-        let mut media_tags = vec![];
-        let mut stream_inf_tags = vec![];
-        let mut i_frame_stream_inf_tags = vec![];
-        let mut session_data_tags = vec![];
-        let mut session_key_tags = vec![];
-
-        for (i, line) in input.parse::<Lines>()?.into_iter().enumerate() {
-            match line {
-                Line::Tag(tag) => {
-                    if i == 0 {
-                        if tag != Tag::ExtM3u(ExtM3u) {
-                            return Err(Error::invalid_input());
-                        }
-                        continue;
+    for (i, line) in input.parse::<Lines>()?.into_iter().enumerate() {
+        match line {
+            Line::Tag(tag) => {
+                if i == 0 {
+                    if tag != Tag::ExtM3u(ExtM3u) {
+                        return Err(Error::invalid_input());
                     }
-                    match tag {
-                        Tag::ExtM3u(_) => {
-                            return Err(Error::invalid_input());
-                        }
-                        Tag::ExtXVersion(_) => {
-                            // This tag can be ignored, because the
-                            // MasterPlaylist will automatically set the
-                            // ExtXVersion tag to correct version!
-                        }
-                        Tag::ExtInf(_)
-                        | Tag::ExtXByteRange(_)
-                        | Tag::ExtXDiscontinuity(_)
-                        | Tag::ExtXKey(_)
-                        | Tag::ExtXMap(_)
-                        | Tag::ExtXProgramDateTime(_)
-                        | Tag::ExtXDateRange(_)
-                        | Tag::ExtXTargetDuration(_)
-                        | Tag::ExtXMediaSequence(_)
-                        | Tag::ExtXDiscontinuitySequence(_)
-                        | Tag::ExtXEndList(_)
-                        | Tag::ExtXPlaylistType(_)
-                        | Tag::ExtXIFramesOnly(_) => {
-                            return Err(Error::custom(format!(
-                                "This tag isn't allowed in a master playlist: {}",
-                                tag
-                            )));
-                        }
-                        Tag::ExtXMedia(t) => {
-                            media_tags.push(t);
-                        }
-                        Tag::ExtXStreamInf(t) => {
-                            stream_inf_tags.push(t);
-                        }
-                        Tag::ExtXIFrameStreamInf(t) => {
-                            i_frame_stream_inf_tags.push(t);
-                        }
-                        Tag::ExtXSessionData(t) => {
-                            session_data_tags.push(t);
-                        }
-                        Tag::ExtXSessionKey(t) => {
-                            session_key_tags.push(t);
-                        }
-                        Tag::ExtXIndependentSegments(t) => {
-                            builder.independent_segments_tag(t);
-                        }
-                        Tag::ExtXStart(t) => {
-                            builder.start_tag(t);
-                        }
-                        _ => {
-                            // [6.3.1. General Client Responsibilities]
-                            // > ignore any unrecognized tags.
-                            // TODO: collect custom tags
+                    continue;
+                }
+                match tag {
+                    Tag::ExtM3u(_) => {
+                        return Err(Error::invalid_input());
+                    }
+                    Tag::UnsupportedVersion(version) => {
+                        if builder.reject_unsupported_version.unwrap_or(false) {
+                            return Err(Error::unknown_protocol_version(version));
                         }
                     }
+                    Tag::ExtXVersion(_) => {
+                        // This tag can be ignored, because the
+                        // MasterPlaylist will automatically set the
+                        // ExtXVersion tag to correct version!
+                    }
+                    Tag::ExtInf(_)
+                    | Tag::ExtXByteRange(_)
+                    | Tag::ExtXBitrate(_)
+                    | Tag::ExtXDiscontinuity(_)
+                    | Tag::ExtXKey(_)
+                    | Tag::ExtXMap(_)
+                    | Tag::ExtXProgramDateTime(_)
+                    | Tag::ExtXDateRange(_)
+                    | Tag::ExtXTargetDuration(_)
+                    | Tag::ExtXMediaSequence(_)
+                    | Tag::ExtXDiscontinuitySequence(_)
+                    | Tag::ExtXEndList(_)
+                    | Tag::ExtXPlaylistType(_)
+                    | Tag::ExtXIFramesOnly(_) => {
+                        return Err(Error::custom(format!(
+                            "This tag isn't allowed in a master playlist: {}",
+                            tag
+                        )));
+                    }
+                    Tag::ExtXMedia(t) => {
+                        document_order.push(MasterTag::Media);
+                        media_tags.push(t);
+                    }
+                    Tag::ExtXStreamInf(t) => {
+                        document_order.push(MasterTag::StreamInf);
+                        stream_inf_tags.push(t);
+                    }
+                    Tag::ExtXIFrameStreamInf(t) => {
+                        document_order.push(MasterTag::IFrameStreamInf);
+                        i_frame_stream_inf_tags.push(t);
+                    }
+                    Tag::ExtXSessionData(t) => {
+                        document_order.push(MasterTag::SessionData);
+                        session_data_tags.push(t);
+                    }
+                    Tag::ExtXSessionKey(t) => {
+                        document_order.push(MasterTag::SessionKey);
+                        session_key_tags.push(t);
+                    }
+                    Tag::ExtXContentSteering(t) => {
+                        document_order.push(MasterTag::ContentSteering);
+                        builder.content_steering_tag(t);
+                    }
+                    Tag::ExtXIndependentSegments(t) => {
+                        document_order.push(MasterTag::IndependentSegments);
+                        builder.independent_segments_tag(t);
+                    }
+                    Tag::ExtXStart(t) => {
+                        document_order.push(MasterTag::Start);
+                        builder.start_tag(t);
+                    }
+                    Tag::Unknown(t) => {
+                        // [6.3.1. General Client Responsibilities]
+                        // > ignore any unrecognized tags.
+                        unknown_tags.push(t);
+                    }
                 }
-                Line::Uri(uri) => {
-                    return Err(Error::custom(format!("Unexpected URI: {:?}", uri)));
-                }
+            }
+            Line::Uri(uri) => {
+                return Err(Error::custom(format!("Unexpected URI: {:?}", uri)));
             }
         }
+    }
+
+    builder.media_tags(media_tags);
+    builder.stream_inf_tags(stream_inf_tags);
+    builder.i_frame_stream_inf_tags(i_frame_stream_inf_tags);
+    builder.session_data_tags(session_data_tags);
+    builder.session_key_tags(session_key_tags);
+
+    let mut playlist = builder.build().map_err(Error::builder_error)?;
+    playlist.document_order = document_order;
+    playlist.unknown_tags = unknown_tags;
 
-        builder.media_tags(media_tags);
-        builder.stream_inf_tags(stream_inf_tags);
-        builder.i_frame_stream_inf_tags(i_frame_stream_inf_tags);
-        builder.session_data_tags(session_data_tags);
-        builder.session_key_tags(session_key_tags);
+    Ok(playlist)
+}
+
+impl FromStr for MasterPlaylist {
+    type Err = Error;
 
-        builder.build().map_err(Error::builder_error)
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_master_playlist(input, &mut Self::builder())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::AttributeOrder;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -481,4 +1224,469 @@ mod tests {
         let playlist = input.parse::<MasterPlaylist>().unwrap();
         assert_eq!(playlist.to_string(), input);
     }
+
+    #[test]
+    fn test_default_renditions() {
+        let error = "#EXTM3U\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",DEFAULT=YES\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+        http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "BuilderError: CustomError: Multiple default renditions in the AUDIO group \
+             \"aac\": [\"English\", \"Deutsch\"]"
+        );
+
+        // the subtitles group is not referenced by any variant stream:
+        "#EXTM3U\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",DEFAULT=NO\n\
+        #EXT-X-MEDIA:TYPE=SUBTITLES,URI=\"en.m3u8\",GROUP-ID=\"subs\",NAME=\"English\",DEFAULT=YES\n\
+        #EXT-X-MEDIA:TYPE=SUBTITLES,URI=\"de.m3u8\",GROUP-ID=\"subs\",NAME=\"Deutsch\",DEFAULT=YES\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+        http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pathways() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-A\"\n\
+        http://a.example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=7680000,PATHWAY-ID=\"CDN-A\"\n\
+        http://a.example.com/hi/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-B\"\n\
+        http://b.example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=7680000,PATHWAY-ID=\"CDN-B\"\n\
+        http://b.example.com/hi/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.pathways(), vec!["CDN-A", "CDN-B"]);
+
+        assert_eq!(
+            playlist
+                .variants_for_pathway("CDN-B")
+                .iter()
+                .map(|t| t.uri().as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "http://b.example.com/low/index.m3u8",
+                "http://b.example.com/hi/index.m3u8"
+            ]
+        );
+        assert!(playlist.variants_for_pathway(".").is_empty());
+    }
+
+    #[test]
+    fn test_content_steering() {
+        let input = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-A\"\n\
+        http://a.example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-B\"\n\
+        http://b.example.com/low/index.m3u8\n\
+        #EXT-X-CONTENT-STEERING:SERVER-URI=\"https://example.com/manifest.json\",\
+        PATHWAY-ID=\"CDN-B\"\n";
+
+        let playlist = input.parse::<MasterPlaylist>().unwrap();
+
+        assert_eq!(
+            playlist.content_steering(),
+            &Some(ExtXContentSteering::with_pathway_id(
+                "https://example.com/manifest.json",
+                "CDN-B"
+            ))
+        );
+        assert!(playlist.unknown_tags().is_empty());
+        assert_eq!(playlist.to_string(), input);
+        assert_eq!(
+            playlist.to_string().parse::<MasterPlaylist>().unwrap(),
+            playlist
+        );
+    }
+
+    #[test]
+    fn test_preserve_document_order() {
+        let input = "#EXTM3U\n\
+        #EXT-X-MEDIA:TYPE=VIDEO,URI=\"low/main/audio-video.m3u8\",GROUP-ID=\"low\",NAME=\"Main\",DEFAULT=YES\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,VIDEO=\"low\"\n\
+        low/main/audio-video.m3u8\n\
+        #EXT-X-MEDIA:TYPE=VIDEO,URI=\"hi/main/audio-video.m3u8\",GROUP-ID=\"hi\",NAME=\"Main\",DEFAULT=YES\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=7680000,VIDEO=\"hi\"\n\
+        hi/main/audio-video.m3u8\n";
+
+        let playlist = input.parse::<MasterPlaylist>().unwrap();
+        let options = WriteOptions {
+            preserve_document_order: true,
+            ..WriteOptions::default()
+        };
+        assert_eq!(playlist.to_string_with(&options), input);
+
+        // the recorded order is not part of the comparison:
+        assert_eq!(
+            playlist,
+            MasterPlaylist::builder()
+                .media_tags(playlist.media_tags().to_vec())
+                .stream_inf_tags(playlist.stream_inf_tags().to_vec())
+                .build()
+                .unwrap()
+        );
+
+        // the order is kept for the remaining tags of an audio-only playlist:
+        let input = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\",AUDIO=\"aac\"\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"video\",NAME=\"Main\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\",AUDIO=\"aac\"\n\
+        http://example.com/audio/index.m3u8\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\"\n";
+
+        assert_eq!(
+            input
+                .parse::<MasterPlaylist>()
+                .unwrap()
+                .audio_only()
+                .unwrap()
+                .to_string_with(&options),
+            "#EXTM3U\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\",AUDIO=\"aac\"\n\
+            http://example.com/audio/index.m3u8\n\
+            #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\"\n"
+        );
+
+        // by default the tags are grouped:
+        assert_eq!(
+            playlist.to_string(),
+            "#EXTM3U\n\
+            #EXT-X-MEDIA:TYPE=VIDEO,URI=\"low/main/audio-video.m3u8\",GROUP-ID=\"low\",NAME=\"Main\",DEFAULT=YES\n\
+            #EXT-X-MEDIA:TYPE=VIDEO,URI=\"hi/main/audio-video.m3u8\",GROUP-ID=\"hi\",NAME=\"Main\",DEFAULT=YES\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=1280000,VIDEO=\"low\"\n\
+            low/main/audio-video.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=7680000,VIDEO=\"hi\"\n\
+            hi/main/audio-video.m3u8\n"
+        );
+    }
+
+    #[test]
+    fn test_forward_declared_groups() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\",CLOSED-CAPTIONS=\"cc\"\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES\n\
+        #EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID=\"cc\",NAME=\"English\",INSTREAM-ID=\"CC1\"\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.stream_inf_tags()[0].audio(),
+            &Some("aac".to_string())
+        );
+        assert_eq!(playlist.media_tags().len(), 2);
+
+        // a group, that is never declared, is still rejected:
+        assert!("#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"mp3\",NAME=\"English\",DEFAULT=YES\n"
+            .parse::<MasterPlaylist>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_bandwidth_gaps() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=6000000\n\
+        http://example.com/high/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1000000\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2000000\n\
+        http://example.com/mid/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2000000,PATHWAY-ID=\"CDN-B\"\n\
+        http://b.example.com/mid/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        let gaps = playlist.bandwidth_gaps();
+
+        assert_eq!(
+            gaps,
+            vec![(1_000_000, 2_000_000, 2.0), (2_000_000, 6_000_000, 3.0)]
+        );
+        assert_eq!(
+            gaps.iter()
+                .filter(|(_, _, ratio)| *ratio > 2.0)
+                .map(|(low, high, _)| (*low, *high))
+                .collect::<Vec<_>>(),
+            vec![(2_000_000, 6_000_000)]
+        );
+
+        assert!(MasterPlaylist::builder()
+            .build()
+            .unwrap()
+            .bandwidth_gaps()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_preload_keys() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-SESSION-KEY:METHOD=AES-128,URI=\"https://www.example.com/a.key\"\n\
+        #EXT-X-SESSION-KEY:METHOD=SAMPLE-AES,URI=\"skd://b\",KEYFORMAT=\"identity\"\n\
+        #EXT-X-SESSION-KEY:METHOD=AES-128,URI=\"https://www.example.com/a.key\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000\n\
+        http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        let keys = playlist.preload_keys();
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys.iter()
+                .map(|k| k.uri().clone().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["https://www.example.com/a.key", "skd://b"]
+        );
+    }
+
+    #[test]
+    fn test_sorted_renditions() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",URI=\"subs/en.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",URI=\"de.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Francais\",URI=\"fr.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,AUTOSELECT=YES,\
+        URI=\"en.m3u8\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\",SUBTITLES=\"subs\"\n\
+        http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist
+                .sorted_renditions()
+                .iter()
+                .map(|m| (m.media_type(), m.name().as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (MediaType::Audio, "English"),
+                (MediaType::Audio, "Deutsch"),
+                (MediaType::Audio, "Francais"),
+                (MediaType::Subtitles, "English"),
+            ]
+        );
+
+        // the order of the tags in the playlist is not changed:
+        assert_eq!(playlist.media_tags()[0].media_type(), MediaType::Subtitles);
+    }
+
+    #[test]
+    fn test_audio_only() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-INDEPENDENT-SEGMENTS\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,AUTOSELECT=YES,\
+        LANGUAGE=\"en\",URI=\"audio/en.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",LANGUAGE=\"de\",\
+        URI=\"audio/de.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",URI=\"subs/en.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID=\"cc\",NAME=\"English\",INSTREAM-ID=\"CC1\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\",AUDIO=\"aac\",SUBTITLES=\"subs\",\
+        CLOSED-CAPTIONS=\"cc\"\n\
+        http://example.com/audio/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\",\
+        RESOLUTION=640x360,AUDIO=\"aac\",SUBTITLES=\"subs\",CLOSED-CAPTIONS=\"cc\"\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2560000,CODECS=\"avc1.4d401f,mp4a.40.2\",\
+        AUDIO=\"aac\",SUBTITLES=\"subs\",CLOSED-CAPTIONS=\"cc\"\n\
+        http://example.com/mid/index.m3u8\n\
+        #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,URI=\"http://example.com/low/iframe.m3u8\"\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        let audio_only = playlist.audio_only().unwrap();
+
+        assert_eq!(
+            audio_only.to_string(),
+            "#EXTM3U\n\
+             #EXT-X-INDEPENDENT-SEGMENTS\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,\
+             AUTOSELECT=YES,LANGUAGE=\"en\",URI=\"audio/en.m3u8\"\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",LANGUAGE=\"de\",\
+             URI=\"audio/de.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\",AUDIO=\"aac\"\n\
+             http://example.com/audio/index.m3u8\n"
+                .parse::<MasterPlaylist>()
+                .unwrap()
+                .to_string()
+        );
+        assert!(audio_only.i_frame_stream_inf_tags().is_empty());
+
+        // a playlist without an audio-only variant stream:
+        assert!("#EXTM3U\n\
+                 #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\"\n\
+                 http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap()
+            .audio_only()
+            .is_err());
+    }
+
+    #[test]
+    fn test_sustainable_variants() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=800000\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=6000000,AVERAGE-BANDWIDTH=5000000\n\
+        http://example.com/high/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2000000,AVERAGE-BANDWIDTH=1500000\n\
+        http://example.com/mid/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2000000,AVERAGE-BANDWIDTH=1800000\n\
+        http://example.com/mid-alt/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=3000000,AVERAGE-BANDWIDTH=3500000\n\
+        http://example.com/broken/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        let uris = |throughput, safety_factor| {
+            playlist
+                .sustainable_variants(throughput, safety_factor)
+                .iter()
+                .map(|t| t.uri().as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(uris(500_000, 1.0).is_empty());
+        assert_eq!(
+            uris(1_000_000, 1.0),
+            vec!["http://example.com/low/index.m3u8"]
+        );
+        assert_eq!(
+            uris(2_000_000, 1.0),
+            vec![
+                "http://example.com/mid-alt/index.m3u8",
+                "http://example.com/mid/index.m3u8",
+                "http://example.com/low/index.m3u8",
+            ]
+        );
+        // the average bandwidth exceeds the throughput:
+        assert_eq!(
+            uris(3_000_000, 1.0),
+            vec![
+                "http://example.com/mid-alt/index.m3u8",
+                "http://example.com/mid/index.m3u8",
+                "http://example.com/low/index.m3u8",
+            ]
+        );
+        assert_eq!(
+            uris(7_200_000, 1.2),
+            vec![
+                "http://example.com/high/index.m3u8",
+                "http://example.com/broken/index.m3u8",
+                "http://example.com/mid-alt/index.m3u8",
+                "http://example.com/mid/index.m3u8",
+                "http://example.com/low/index.m3u8",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reject_unsupported_version() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-VERSION:99\n\
+                        #EXT-X-STREAM-INF:BANDWIDTH=1280000\n\
+                        http://example.com/low/index.m3u8\n";
+
+        assert_eq!(
+            playlist
+                .parse::<MasterPlaylist>()
+                .unwrap()
+                .stream_inf_tags()
+                .len(),
+            1
+        );
+        assert!(MasterPlaylist::builder()
+            .reject_unsupported_version(true)
+            .parse(playlist)
+            .is_err());
+        assert_eq!(
+            MasterPlaylist::builder()
+                .reject_unsupported_version(true)
+                .parse(&playlist.replace("VERSION:99", "VERSION:7"))
+                .unwrap(),
+            playlist.parse::<MasterPlaylist>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_apple_compatible_media_attribute_order() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,URI=\"audio/en.m3u8\",GROUP-ID=\"aac\",LANGUAGE=\"en\",\
+        NAME=\"English\",DEFAULT=YES,AUTOSELECT=YES,CHANNELS=\"2\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+        http://example.com/low/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions::default()),
+            playlist.to_string()
+        );
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions {
+                media_attribute_order: AttributeOrder::AppleCompatible,
+                ..WriteOptions::default()
+            }),
+            "#EXTM3U\n\
+             #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",LANGUAGE=\"en\",\
+             DEFAULT=YES,AUTOSELECT=YES,CHANNELS=\"2\",URI=\"audio/en.m3u8\"\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+             http://example.com/low/index.m3u8\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_variants_for_hdcp() {
+        let playlist = "#EXTM3U\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=800000\n\
+        http://example.com/low/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2000000,HDCP-LEVEL=NONE\n\
+        http://example.com/mid/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=4000000,HDCP-LEVEL=TYPE-0\n\
+        http://example.com/hd/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=12000000,HDCP-LEVEL=TYPE-1\n\
+        http://example.com/uhd/index.m3u8\n"
+            .parse::<MasterPlaylist>()
+            .unwrap();
+
+        let uris = |max_level| {
+            playlist
+                .variants_for_hdcp(max_level)
+                .iter()
+                .map(|t| t.uri().as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            uris(HdcpLevel::None),
+            vec![
+                "http://example.com/low/index.m3u8",
+                "http://example.com/mid/index.m3u8",
+            ]
+        );
+        assert_eq!(
+            uris(HdcpLevel::Type0),
+            vec![
+                "http://example.com/low/index.m3u8",
+                "http://example.com/mid/index.m3u8",
+                "http://example.com/hd/index.m3u8",
+            ]
+        );
+        assert_eq!(uris(HdcpLevel::Type1).len(), 4);
+    }
 }
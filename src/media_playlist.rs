@@ -1,23 +1,53 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
+use std::io;
+use std::iter;
 use std::str::FromStr;
 use std::time::Duration;
 
 use derive_builder::Builder;
 
+use crate::attribute::AttributePairs;
 use crate::line::{Line, Lines, Tag};
-use crate::media_segment::MediaSegment;
+use crate::media_segment::{MediaSegment, MediaSegmentBuilder};
 use crate::tags::{
-    ExtM3u, ExtXDiscontinuitySequence, ExtXEndList, ExtXIFramesOnly, ExtXIndependentSegments,
-    ExtXMediaSequence, ExtXPlaylistType, ExtXStart, ExtXTargetDuration, ExtXVersion,
+    ExtM3u, ExtXDateRange, ExtXDiscontinuitySequence, ExtXEndList, ExtXIFramesOnly,
+    ExtXIndependentSegments, ExtXMap, ExtXMediaSequence, ExtXPlaylistType, ExtXStart,
+    ExtXTargetDuration, ExtXVersion,
 };
-use crate::types::ProtocolVersion;
-use crate::{Encrypted, Error, RequiredVersion};
+use crate::types::{
+    DecimalFloatingPoint, EncryptionCoverage, EncryptionMethod, Lint, LintCode, ProtocolVersion,
+    SegmentInfo,
+};
+use crate::utils::{parse_yes_or_no, round_duration, tag, IoWriter};
+use crate::{Encrypted, Error, RequiredVersion, WriteOptions};
 
 /// Media playlist.
-#[derive(Debug, Clone, Builder, PartialEq, PartialOrd)]
-#[builder(build_fn(validate = "Self::validate"))]
-#[builder(setter(into, strip_option))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct MediaPlaylist {
+    target_duration_tag: ExtXTargetDuration,
+    media_sequence_tag: Option<ExtXMediaSequence>,
+    discontinuity_sequence_tag: Option<ExtXDiscontinuitySequence>,
+    playlist_type_tag: Option<ExtXPlaylistType>,
+    i_frames_only_tag: Option<ExtXIFramesOnly>,
+    independent_segments_tag: Option<ExtXIndependentSegments>,
+    start_tag: Option<ExtXStart>,
+    end_list_tag: Option<ExtXEndList>,
+    segments: Vec<MediaSegment>,
+    allowable_excess_duration: Duration,
+    unknown_tags: Vec<String>,
+}
+
+/// The fields of a [`MediaPlaylist`] and the options of its builder, which are
+/// not part of the playlist itself.
+// The options are only read from the builder, so they are never read here.
+#[allow(dead_code)]
+#[derive(Builder)]
+#[builder(name = "MediaPlaylistBuilder", public)]
+#[builder(build_fn(validate = "Self::validate", name = "build_fields", private))]
+#[builder(setter(into, strip_option))]
+struct MediaPlaylistFields {
     /// Sets the [`ExtXTargetDuration`] tag.
     target_duration_tag: ExtXTargetDuration,
     #[builder(default)]
@@ -54,6 +84,65 @@ pub struct MediaPlaylist {
     /// The default value is `Duration::from_secs(0)`.
     #[builder(default = "Duration::from_secs(0)")]
     allowable_excess_duration: Duration,
+    /// Enables additional validations, that reject playlists, which are
+    /// likely to be broken.
+    ///
+    /// # Error
+    /// If this is enabled, the invocation of `MediaPlaylistBuilder::build()`
+    /// method will fail, if
+    /// - the `URI` of a media segment, its [`ExtXMap`] or one of its
+    ///   [`ExtXKey`]s contains unencoded whitespace.
+    ///
+    /// Parsing a playlist with [`MediaPlaylistBuilder::parse`] will fail with
+    /// a more descriptive error, if
+    /// - an [`ExtXMap`] tag is not followed by any media segment.
+    ///
+    /// [`ExtXMap`]: crate::tags::ExtXMap
+    /// [`ExtXKey`]: crate::tags::ExtXKey
+    ///
+    /// The default value is `false`.
+    #[builder(default)]
+    strict: bool,
+    /// Raises the [`ExtXTargetDuration`] to the largest rounded segment
+    /// duration, instead of rejecting segments, that exceed it.
+    ///
+    /// If this is enabled, the invocation of `MediaPlaylistBuilder::build()`
+    /// will not fail because of a too large segment duration and
+    /// [`MediaPlaylist::target_duration_tag`] returns (and the playlist
+    /// emits) the raised [`ExtXTargetDuration`].
+    ///
+    /// The default value is `false`.
+    #[builder(default)]
+    auto_raise_target_duration: bool,
+    /// Rejects segments, whose [`ExtXProgramDateTime`] is earlier than the
+    /// one of the previous segment in the same continuous range.
+    ///
+    /// # Error
+    /// If this is enabled, the invocation of `MediaPlaylistBuilder::build()`
+    /// will fail, if the date and time of a segment is earlier than the date
+    /// and time of the previous segment, unless there is an
+    /// [`ExtXDiscontinuity`] between them.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`ExtXProgramDateTime`]: crate::tags::ExtXProgramDateTime
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    #[builder(default)]
+    enforce_pdt_monotonicity: bool,
+    /// Rejects playlists, that declare a version newer than
+    /// [`ProtocolVersion::MAX_SUPPORTED`] in their [`ExtXVersion`] tag.
+    ///
+    /// By default such playlists are parsed leniently and the declared
+    /// version is ignored, like every [`ExtXVersion`] tag.
+    ///
+    /// # Error
+    /// If this is enabled, parsing a playlist with
+    /// [`MediaPlaylistBuilder::parse`] will fail with an `Unknown Protocol
+    /// version` error, if it declares an unsupported version.
+    ///
+    /// The default value is `false`.
+    #[builder(default)]
+    reject_unsupported_version: bool,
 }
 
 impl MediaPlaylistBuilder {
@@ -63,41 +152,93 @@ impl MediaPlaylistBuilder {
                 .map_err(|e| e.to_string())?;
         }
 
+        // CHECK: the media sequence number of the last segment
+        if let (Some(Some(tag)), Some(segments)) = (&self.media_sequence_tag, &self.segments) {
+            if let Some(last_index) = segments.len().checked_sub(1) {
+                if tag.seq_num().checked_add(last_index as u64).is_none() {
+                    return Err(format!(
+                        "The media sequence number of the last segment exceeds u64::MAX: \
+                         media_sequence={}, segments={}",
+                        tag.seq_num(),
+                        segments.len()
+                    ));
+                }
+            }
+        }
+
         Ok(())
     }
 
     fn validate_media_segments(&self, target_duration: Duration) -> crate::Result<()> {
         let mut last_range_uri = None;
+        let mut last_date_time = None;
+        let mut has_bitrate = false;
         if let Some(segments) = &self.segments {
             for s in segments {
                 // CHECK: `#EXT-X-TARGETDURATION`
-                let segment_duration = s.inf_tag().duration();
-                let rounded_segment_duration = {
-                    if segment_duration.subsec_nanos() < 500_000_000 {
-                        Duration::from_secs(segment_duration.as_secs())
-                    } else {
-                        Duration::from_secs(segment_duration.as_secs() + 1)
+                if !self.auto_raise_target_duration.unwrap_or(false) {
+                    check_segment_duration(
+                        s,
+                        s.inf_tag().duration(),
+                        target_duration,
+                        self.allowable_excess_duration.unwrap_or_default(),
+                    )?;
+                }
+
+                // CHECK: whitespace in the `URI`s
+                if self.strict.unwrap_or(false) {
+                    let uris = iter::once(s.uri())
+                        .chain(s.map_tag().iter().map(|t| t.uri()))
+                        .chain(s.keys().iter().filter_map(|k| k.uri().as_ref()));
+
+                    for uri in uris {
+                        if uri.chars().any(char::is_whitespace) {
+                            return Err(Error::custom(format!(
+                                "URI contains whitespace: {:?}",
+                                uri
+                            )));
+                        }
                     }
-                };
+                }
 
-                let max_segment_duration = {
-                    if let Some(value) = &self.allowable_excess_duration {
-                        target_duration + *value
-                    } else {
-                        target_duration
+                // CHECK: `#EXT-X-PROGRAM-DATE-TIME`
+                if s.discontinuity_tag().is_some() {
+                    last_date_time = None;
+                }
+
+                if let Some(tag) = s.program_date_time_tag() {
+                    let date_time = tag.date_time();
+
+                    match last_date_time {
+                        Some(last_date_time)
+                            if self.enforce_pdt_monotonicity.unwrap_or(false)
+                                && date_time < last_date_time =>
+                        {
+                            return Err(Error::custom(format!(
+                                "Program date time goes backwards: {} -> {}, uri={:?}",
+                                last_date_time.to_rfc3339(),
+                                date_time.to_rfc3339(),
+                                s.uri()
+                            )));
+                        }
+                        _ => {}
                     }
-                };
 
-                if rounded_segment_duration > max_segment_duration {
+                    last_date_time = Some(date_time);
+                }
+
+                // CHECK: `#EXT-X-BITRATE`
+                // A bitrate applies to every following segment, until the next
+                // `#EXT-X-BITRATE` tag, so there is no way to remove it again.
+                if has_bitrate && s.bitrate_tag().is_none() {
                     return Err(Error::custom(format!(
-                        "Too large segment duration: actual={:?}, max={:?}, target_duration={:?}, uri={:?}",
-                        segment_duration,
-                        max_segment_duration,
-                        target_duration,
+                        "Segment without a bitrate after a segment with a bitrate: uri={:?}",
                         s.uri()
                     )));
                 }
 
+                has_bitrate |= s.bitrate_tag().is_some();
+
                 // CHECK: `#EXT-X-BYTE-RANGE`
                 if let Some(tag) = s.byte_range_tag() {
                     if tag.to_range().start().is_none() {
@@ -116,6 +257,53 @@ impl MediaPlaylistBuilder {
         Ok(())
     }
 
+    /// Builds a new [`MediaPlaylist`].
+    ///
+    /// If `auto_raise_target_duration` is enabled, the [`ExtXTargetDuration`]
+    /// is raised to the largest rounded segment duration.
+    ///
+    /// # Errors
+    /// If a required field has not been initialized or if the playlist is
+    /// invalid.
+    pub fn build(&self) -> Result<MediaPlaylist, String> {
+        let MediaPlaylistFields {
+            target_duration_tag,
+            media_sequence_tag,
+            discontinuity_sequence_tag,
+            playlist_type_tag,
+            i_frames_only_tag,
+            independent_segments_tag,
+            start_tag,
+            end_list_tag,
+            segments,
+            allowable_excess_duration,
+            auto_raise_target_duration,
+            strict: _,
+            enforce_pdt_monotonicity: _,
+            reject_unsupported_version: _,
+        } = self.build_fields()?;
+
+        let mut playlist = MediaPlaylist {
+            target_duration_tag,
+            media_sequence_tag,
+            discontinuity_sequence_tag,
+            playlist_type_tag,
+            i_frames_only_tag,
+            independent_segments_tag,
+            start_tag,
+            end_list_tag,
+            segments,
+            allowable_excess_duration,
+            unknown_tags: vec![],
+        };
+
+        if auto_raise_target_duration {
+            playlist.raise_target_duration();
+        }
+
+        Ok(playlist)
+    }
+
     /// Adds a media segment to the resulting playlist.
     pub fn push_segment<VALUE: Into<MediaSegment>>(&mut self, value: VALUE) -> &mut Self {
         if let Some(segments) = &mut self.segments {
@@ -128,7 +316,135 @@ impl MediaPlaylistBuilder {
 
     /// Parse the rest of the [`MediaPlaylist`] from an m3u8 file.
     pub fn parse(&mut self, input: &str) -> crate::Result<MediaPlaylist> {
-        parse_media_playlist(input, self)
+        parse_media_playlist(input, self, &mut |_| {}, &mut vec![])
+    }
+
+    /// Parse the rest of the [`MediaPlaylist`] from an m3u8 file and returns
+    /// it together with its [`Lint`]s.
+    ///
+    /// In addition to the [`Lint`]s of [`MediaPlaylist::lint`], this reports
+    /// the deviations, that can only be found in the document, like an
+    /// [`ExtXKey`] tag, that is equal to the key already in effect. They are
+    /// appended to the [`Lint`]s of the playlist.
+    ///
+    /// [`ExtXKey`]: crate::tags::ExtXKey
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::types::LintCode;
+    ///
+    /// let (playlist, lints) = MediaPlaylist::builder().parse_with_lints(
+    ///     "#EXTM3U\n\
+    ///      #EXT-X-TARGETDURATION:10\n\
+    ///      #EXT-X-PLAYLIST-TYPE:VOD\n\
+    ///      #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+    ///      #EXTINF:10,\n\
+    ///      http://media.example.com/first.ts\n\
+    ///      #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+    ///      #EXTINF:10,\n\
+    ///      http://media.example.com/second.ts\n\
+    ///      #EXT-X-ENDLIST",
+    /// )?;
+    ///
+    /// assert!(playlist.lint().is_empty());
+    /// assert_eq!(lints.len(), 1);
+    /// assert_eq!(lints[0].code(), LintCode::RedundantKey);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn parse_with_lints(&mut self, input: &str) -> crate::Result<(MediaPlaylist, Vec<Lint>)> {
+        let mut document_lints = vec![];
+        let playlist = parse_media_playlist(input, self, &mut |_| {}, &mut document_lints)?;
+
+        let mut lints = playlist.lint();
+        lints.extend(document_lints);
+
+        Ok((playlist, lints))
+    }
+
+    /// Registers a callback, that is invoked with the number of parsed
+    /// [`MediaSegment`]s every [`ProgressParser::interval`] segments, while
+    /// parsing a playlist with [`ProgressParser::parse`].
+    ///
+    /// [`ProgressParser::interval`]: crate::ProgressParser::interval
+    /// [`ProgressParser::parse`]: crate::ProgressParser::parse
+    ///
+    /// This is a best-effort hook to report the progress of parsing large
+    /// playlists, there is no guarantee on how often the callback is invoked.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts";
+    ///
+    /// let mut progress = vec![];
+    ///
+    /// MediaPlaylist::builder()
+    ///     .on_progress(|count| progress.push(count))
+    ///     .interval(1)
+    ///     .parse(playlist)?;
+    ///
+    /// assert_eq!(progress, vec![1, 2]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn on_progress<F: FnMut(usize)>(&mut self, callback: F) -> ProgressParser<'_, F> {
+        ProgressParser {
+            builder: self,
+            callback,
+            interval: ProgressParser::<F>::DEFAULT_INTERVAL,
+        }
+    }
+}
+
+/// Parses a [`MediaPlaylist`] and reports the progress to a callback.
+///
+/// See `MediaPlaylistBuilder::on_progress`.
+pub struct ProgressParser<'a, F> {
+    builder: &'a mut MediaPlaylistBuilder,
+    callback: F,
+    interval: usize,
+}
+
+impl<'a, F: FnMut(usize)> ProgressParser<'a, F> {
+    const DEFAULT_INTERVAL: usize = 1000;
+
+    /// Sets the number of [`MediaSegment`]s, that have to be parsed between
+    /// two invocations of the callback.
+    ///
+    /// The default value is `1000`, a value of `0` is treated like `1`.
+    pub fn interval(&mut self, value: usize) -> &mut Self {
+        self.interval = value.max(1);
+        self
+    }
+
+    /// Parse the rest of the [`MediaPlaylist`] from an m3u8 file.
+    pub fn parse(&mut self, input: &str) -> crate::Result<MediaPlaylist> {
+        let interval = self.interval;
+        let callback = &mut self.callback;
+
+        parse_media_playlist(
+            input,
+            self.builder,
+            &mut |count| {
+                if count % interval == 0 {
+                    callback(count);
+                }
+            },
+            &mut vec![],
+        )
+    }
+}
+
+impl<'a, F> fmt::Debug for ProgressParser<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressParser")
+            .field("interval", &self.interval)
+            .finish()
     }
 }
 
@@ -152,9 +468,143 @@ impl MediaPlaylist {
     /// Returns a builder for [`MediaPlaylist`].
     pub fn builder() -> MediaPlaylistBuilder { MediaPlaylistBuilder::default() }
 
+    /// Parses a [`MediaPlaylist`], that uses `EXT-X-DEFINE` tags to define
+    /// variables.
+    ///
+    /// Variables can be defined with a `NAME` and a `VALUE` attribute or
+    /// imported from the query string of the playlist's request with the
+    /// `QUERYPARAM` attribute. The parser doesn't know the request URL, so
+    /// the values of the query parameters have to be provided in
+    /// `query_params`.
+    ///
+    /// Every variable reference (`{$name}`) in a URI line or in the
+    /// attributes of a tag after the `EXT-X-DEFINE` tag is replaced by the
+    /// value of the variable. Comments are not modified and the value of a
+    /// variable is inserted as is, without resolving the references in it.
+    /// The `EXT-X-DEFINE` tags themselves are kept in
+    /// [`MediaPlaylist::unknown_tags`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::collections::HashMap;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-DEFINE:QUERYPARAM=\"token\"\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts?token={$token}";
+    ///
+    /// let mut query_params = HashMap::new();
+    /// query_params.insert("token".to_string(), "secret".to_string());
+    ///
+    /// let playlist = MediaPlaylist::parse_with_query_params(playlist, &query_params)?;
+    ///
+    /// assert_eq!(
+    ///     playlist.segments()[0].uri(),
+    ///     "http://media.example.com/first.ts?token=secret"
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if
+    /// - a `QUERYPARAM` is not present in `query_params`,
+    /// - a variable is defined more than once,
+    /// - a variable is referenced, but not defined before the reference or
+    /// - the playlist is invalid.
+    pub fn parse_with_query_params(
+        input: &str,
+        query_params: &HashMap<String, String>,
+    ) -> crate::Result<Self> {
+        let input = substitute_variables(input, query_params)?;
+
+        input.parse()
+    }
+
+    /// Makes a new [`MediaPlaylist`] from the given target duration and
+    /// [`MediaSegment`] builders.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::{MediaPlaylist, MediaSegment};
+    /// use hls_m3u8::tags::ExtInf;
+    /// use std::time::Duration;
+    ///
+    /// let mut segment = MediaSegment::builder();
+    /// segment
+    ///     .inf_tag(ExtInf::new(Duration::from_secs(10)))
+    ///     .uri("http://media.example.com/first.ts");
+    ///
+    /// let playlist = MediaPlaylist::from_builders(Duration::from_secs(10), vec![segment])?;
+    ///
+    /// assert_eq!(playlist.segments().len(), 1);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns the error of the first builder, that fails to
+    /// build, or an error, if the resulting playlist is invalid.
+    pub fn from_builders<I>(target_duration: Duration, segments: I) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = MediaSegmentBuilder>,
+    {
+        let segments = segments
+            .into_iter()
+            .map(|s| s.build().map_err(Error::builder_error))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        Self::builder()
+            .target_duration_tag(ExtXTargetDuration::new(target_duration))
+            .segments(segments)
+            .build()
+            .map_err(Error::builder_error)
+    }
+
     /// Returns the [`ExtXTargetDuration`] tag contained in the playlist.
+    ///
+    /// If the playlist has been built with `auto_raise_target_duration`
+    /// enabled, this is the largest rounded segment duration, if it exceeds
+    /// the declared target duration.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::tags::ExtXTargetDuration;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::builder()
+    ///     .auto_raise_target_duration(true)
+    ///     .parse(
+    ///         "#EXTM3U\n\
+    ///          #EXT-X-TARGETDURATION:8\n\
+    ///          #EXTINF:9.6,\n\
+    ///          http://media.example.com/first.ts",
+    ///     )?;
+    ///
+    /// assert_eq!(
+    ///     playlist.target_duration_tag(),
+    ///     ExtXTargetDuration::new(Duration::from_secs(10))
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
     pub const fn target_duration_tag(&self) -> ExtXTargetDuration { self.target_duration_tag }
 
+    /// Raises the [`ExtXTargetDuration`] to the largest rounded segment
+    /// duration.
+    fn raise_target_duration(&mut self) {
+        let max_segment_duration = self
+            .segments
+            .iter()
+            .map(|s| round_duration(s.inf_tag().duration()))
+            .max()
+            .unwrap_or_default();
+
+        if max_segment_duration > self.target_duration_tag.duration() {
+            self.target_duration_tag = ExtXTargetDuration::new(max_segment_duration);
+        }
+    }
+
     /// Returns the `EXT-X-MEDIA-SEQUENCE` tag contained in the playlist.
     pub const fn media_sequence_tag(&self) -> Option<ExtXMediaSequence> { self.media_sequence_tag }
 
@@ -183,90 +633,1180 @@ impl MediaPlaylist {
 
     /// Returns the [`MediaSegment`]s contained in the playlist.
     pub const fn segments(&self) -> &Vec<MediaSegment> { &self.segments }
-}
 
-impl RequiredVersion for MediaPlaylist {
-    fn required_version(&self) -> ProtocolVersion {
-        required_version![
-            self.target_duration_tag,
-            self.media_sequence_tag,
-            self.discontinuity_sequence_tag,
-            self.playlist_type_tag,
-            self.i_frames_only_tag,
-            self.independent_segments_tag,
-            self.start_tag,
-            self.end_list_tag,
-            self.segments
-        ]
+    /// Returns all tags, that have not been recognized while parsing the
+    /// playlist, in the order in which they appeared.
+    ///
+    /// # Note
+    /// Unknown tags are ignored by [6.3.1. General Client Responsibilities],
+    /// so they are only collected while parsing and not written by the
+    /// [`Display`] implementation.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-NEW-TAG:VALUE=1\n\
+    ///                 #EXTINF:9.009,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.unknown_tags(), &["#EXT-X-NEW-TAG:VALUE=1".to_string()]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [6.3.1. General Client Responsibilities]:
+    /// https://tools.ietf.org/html/rfc8216#section-6.3.1
+    pub fn unknown_tags(&self) -> &[String] { &self.unknown_tags }
+
+    /// Returns `true`, if this is a Low-Latency HLS playlist, which is the
+    /// case, if it contains an `EXT-X-PART-INF` tag or an
+    /// `EXT-X-SERVER-CONTROL` tag with `CAN-BLOCK-RELOAD=YES`.
+    ///
+    /// Those tags are not modelled by this crate, so they are looked up in
+    /// the [`unknown_tags`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:4\n\
+    ///                 #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=1.0\n\
+    ///                 #EXT-X-PART-INF:PART-TARGET=0.33334\n\
+    ///                 #EXTINF:4.00008,\n\
+    ///                 fileSequence268.mp4"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert!(playlist.is_low_latency());
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`unknown_tags`]: #method.unknown_tags
+    pub fn is_low_latency(&self) -> bool {
+        self.unknown_tags.iter().any(|t| {
+            if t.starts_with("#EXT-X-PART-INF:") {
+                return true;
+            }
+
+            tag(t, "#EXT-X-SERVER-CONTROL:")
+                .and_then(|input| input.parse::<AttributePairs>())
+                .ok()
+                .and_then(|pairs| parse_yes_or_no(pairs.get("CAN-BLOCK-RELOAD")?).ok())
+                .unwrap_or(false)
+        })
     }
-}
 
-impl fmt::Display for MediaPlaylist {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "{}", ExtM3u)?;
-        if self.required_version() != ProtocolVersion::V1 {
-            writeln!(f, "{}", ExtXVersion::new(self.required_version()))?;
-        }
-        writeln!(f, "{}", self.target_duration_tag)?;
-        if let Some(value) = &self.media_sequence_tag {
-            writeln!(f, "{}", value)?;
-        }
-        if let Some(value) = &self.discontinuity_sequence_tag {
-            writeln!(f, "{}", value)?;
-        }
-        if let Some(value) = &self.playlist_type_tag {
-            writeln!(f, "{}", value)?;
-        }
-        if let Some(value) = &self.i_frames_only_tag {
-            writeln!(f, "{}", value)?;
-        }
-        if let Some(value) = &self.independent_segments_tag {
-            writeln!(f, "{}", value)?;
+    /// Returns how far behind the end of a live playlist a client should
+    /// start the playback.
+    ///
+    /// This is the `HOLD-BACK` attribute of the `EXT-X-SERVER-CONTROL` tag or,
+    /// if it is missing, three times the target duration (see
+    /// [6.3.3. Playing the Media Playlist File]).
+    ///
+    /// The `EXT-X-SERVER-CONTROL` tag is not modelled by this crate, so it is
+    /// looked up in the [`unknown_tags`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:6\n\
+    ///                 #EXTINF:6,\n\
+    ///                 http://media.example.com/first.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(
+    ///     playlist.estimated_presentation_delay(),
+    ///     Duration::from_secs(18)
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`unknown_tags`]: #method.unknown_tags
+    /// [6.3.3. Playing the Media Playlist File]:
+    /// https://tools.ietf.org/html/rfc8216#section-6.3.3
+    pub fn estimated_presentation_delay(&self) -> Duration {
+        self.unknown_tags
+            .iter()
+            .find_map(|t| {
+                tag(t, "#EXT-X-SERVER-CONTROL:")
+                    .and_then(|input| input.parse::<AttributePairs>())
+                    .ok()
+                    .and_then(|pairs| pairs.get("HOLD-BACK")?.parse::<DecimalFloatingPoint>().ok())
+            })
+            .map(|hold_back| Duration::from_secs_f64(hold_back.as_f64()))
+            .unwrap_or_else(|| self.target_duration_tag().duration() * 3)
+    }
+
+    /// Returns `true`, if the playlist belongs to a live stream, that has just
+    /// been started and did not publish any [`MediaSegment`] yet.
+    ///
+    /// This is the case, if the playlist
+    /// - has no [`ExtXEndList`] tag and is not a [`ExtXPlaylistType::Vod`]
+    ///   playlist,
+    /// - contains no [`MediaSegment`]s and
+    /// - has an `EXT-X-SERVER-CONTROL` tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:4\n\
+    ///                 #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,HOLD-BACK=12.0\n"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert!(playlist.is_initializing());
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn is_initializing(&self) -> bool {
+        let is_live =
+            self.end_list_tag.is_none() && self.playlist_type_tag != Some(ExtXPlaylistType::Vod);
+
+        is_live
+            && self.segments.is_empty()
+            && self
+                .unknown_tags
+                .iter()
+                .any(|t| t.starts_with("#EXT-X-SERVER-CONTROL:"))
+    }
+
+    /// Returns the duration of media, that has to be buffered, before the
+    /// playback can be started.
+    ///
+    /// This is the sum of the durations of `segments_to_buffer` segments,
+    /// beginning at the segment, where the playback starts:
+    /// - a playlist with an [`ExtXEndList`] tag is played from the first
+    ///   segment.
+    /// - a live playlist is played from the last segment, that starts at least
+    ///   three target durations before the end of the playlist (see
+    ///   [6.3.3. Playing the Media Playlist File]).
+    ///
+    /// # Note
+    /// `segments_to_buffer` is clamped to the number of available segments.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:9.5,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:9.5,\n\
+    ///                 http://media.example.com/second.ts\n\
+    ///                 #EXTINF:4,\n\
+    ///                 http://media.example.com/third.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.startup_buffer(2), Duration::from_secs(19));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [6.3.3. Playing the Media Playlist File]:
+    /// https://tools.ietf.org/html/rfc8216#section-6.3.3
+    pub fn startup_buffer(&self, segments_to_buffer: usize) -> Duration {
+        let start = {
+            if self.end_list_tag.is_some() {
+                0
+            } else {
+                let hold_back = self.target_duration_tag().duration() * 3;
+                let mut duration = Duration::from_secs(0);
+
+                self.segments
+                    .iter()
+                    .rposition(|s| {
+                        duration += s.inf_tag().duration();
+                        duration >= hold_back
+                    })
+                    .unwrap_or(0)
+            }
+        };
+
+        self.segments
+            .iter()
+            .skip(start)
+            .take(segments_to_buffer)
+            .map(|s| s.inf_tag().duration())
+            .sum()
+    }
+
+    /// Returns a report about the encryption of the [`MediaSegment`]s.
+    ///
+    /// A segment is encrypted, if any of its (inherited) [`ExtXKey`] tags has
+    /// a method other than [`EncryptionMethod::None`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::types::EncryptionMethod;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-KEY:METHOD=AES-128,URI=\"https://priv.example.com/key.php?r=52\"\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let coverage = playlist.encryption_coverage();
+    ///
+    /// assert_eq!(coverage.encrypted_segments(), 1);
+    /// assert_eq!(coverage.clear_segments(), 1);
+    /// assert!(coverage.methods().contains(&EncryptionMethod::Aes128));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXKey`]: crate::tags::ExtXKey
+    pub fn encryption_coverage(&self) -> EncryptionCoverage {
+        let mut result = EncryptionCoverage::default();
+        let mut has_encrypted_segment = false;
+        let mut has_pending_clear_segment = false;
+
+        for segment in &self.segments {
+            if segment.is_encrypted() {
+                result.encrypted_segments += 1;
+                result.methods.extend(
+                    segment
+                        .keys()
+                        .iter()
+                        .map(|k| k.method())
+                        .filter(|m| *m != EncryptionMethod::None),
+                );
+
+                if has_pending_clear_segment {
+                    result.has_clear_gap = true;
+                }
+
+                has_encrypted_segment = true;
+                has_pending_clear_segment = false;
+            } else {
+                result.clear_segments += 1;
+                has_pending_clear_segment = has_encrypted_segment;
+            }
         }
-        if let Some(value) = &self.start_tag {
-            writeln!(f, "{}", value)?;
+
+        result
+    }
+
+    /// Returns warnings about deviations from the recommendations of the
+    /// specification, that do not make the playlist invalid.
+    ///
+    /// See [`LintCode`] for the deviations, that are reported.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::types::LintCode;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let lints = playlist.lint();
+    ///
+    /// assert_eq!(lints.len(), 1);
+    /// assert_eq!(lints[0].code(), LintCode::MissingPlaylistType);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut result = vec![];
+
+        if self.end_list_tag.is_some() && self.playlist_type_tag.is_none() {
+            result.push(Lint::new(
+                LintCode::MissingPlaylistType,
+                "The playlist has an EXT-X-ENDLIST tag, but no EXT-X-PLAYLIST-TYPE tag",
+            ));
         }
-        for segment in &self.segments {
-            write!(f, "{}", segment)?;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let keys = segment.keys();
+
+            if keys.iter().enumerate().any(|(j, k)| {
+                keys[j + 1..]
+                    .iter()
+                    .any(|o| o.key_format() == k.key_format())
+            }) {
+                result.push(Lint::new(
+                    LintCode::RedundantKey,
+                    format!(
+                        "The segment {} has more than one EXT-X-KEY with the same KEYFORMAT",
+                        i
+                    ),
+                ));
+            }
         }
-        if let Some(value) = &self.end_list_tag {
-            writeln!(f, "{}", value)?;
+
+        let longest_duration = self
+            .segments
+            .iter()
+            .map(|s| round_duration(s.inf_tag().duration()))
+            .max();
+
+        let is_live =
+            self.end_list_tag.is_none() && self.playlist_type_tag != Some(ExtXPlaylistType::Vod);
+
+        if let Some(longest_duration) = longest_duration {
+            let target_duration = self.target_duration_tag().duration();
+
+            // only a live playlist is reloaded by the clients:
+            if is_live && target_duration > longest_duration + Duration::from_secs(1) {
+                result.push(Lint::new(
+                    LintCode::LargeTargetDuration,
+                    format!(
+                        "The target duration ({}s) is larger than the longest rounded segment \
+                         duration ({}s)",
+                        target_duration.as_secs(),
+                        longest_duration.as_secs()
+                    ),
+                ));
+            }
         }
-        Ok(())
+
+        result
     }
-}
 
-fn parse_media_playlist(
-    input: &str,
-    builder: &mut MediaPlaylistBuilder,
-) -> crate::Result<MediaPlaylist> {
-    let mut segment = MediaSegment::builder();
-    let mut segments = vec![];
+    /// Returns the discontinuity sequence number of each [`MediaSegment`].
+    ///
+    /// The first segment has the number of the [`ExtXDiscontinuitySequence`]
+    /// tag or `0`, if the tag is missing, and every [`ExtXDiscontinuity`] tag
+    /// increments the number.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-DISCONTINUITY-SEQUENCE:3\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-DISCONTINUITY\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.segment_discontinuity_sequences(), vec![3, 4]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    pub fn segment_discontinuity_sequences(&self) -> Vec<u64> {
+        let mut discontinuity_sequence = self
+            .discontinuity_sequence_tag
+            .map_or(0, ExtXDiscontinuitySequence::seq_num);
 
-    let mut has_partial_segment = false;
-    let mut has_discontinuity_tag = false;
+        self.segments
+            .iter()
+            .map(|s| {
+                if s.discontinuity_tag().is_some() {
+                    discontinuity_sequence += 1;
+                }
 
-    let mut available_key_tags: Vec<crate::tags::ExtXKey> = vec![];
+                discontinuity_sequence
+            })
+            .collect()
+    }
 
-    for (i, line) in input.parse::<Lines>()?.into_iter().enumerate() {
-        match line {
-            Line::Tag(tag) => {
-                if i == 0 {
-                    if tag != Tag::ExtM3u(ExtM3u) {
-                        return Err(Error::custom("m3u8 doesn't start with #EXTM3U"));
-                    }
-                    continue;
+    /// Returns the number of [`MediaSegment`]s, that have an
+    /// [`ExtXDiscontinuity`] tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-DISCONTINUITY\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.discontinuity_count(), 1);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    pub fn discontinuity_count(&self) -> usize { self.discontinuity_indices().count() }
+
+    /// Returns the indices of the [`MediaSegment`]s, that have an
+    /// [`ExtXDiscontinuity`] tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-DISCONTINUITY\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.discontinuity_indices().collect::<Vec<_>>(), vec![1]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    pub fn discontinuity_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.discontinuity_tag().is_some())
+            .map(|(i, _)| i)
+    }
+
+    /// Returns an iterator over the [`MediaSegment`]s, that also yields the
+    /// index, the absolute media sequence number, the discontinuity sequence
+    /// number and the start offset of every segment.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-MEDIA-SEQUENCE:7\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-DISCONTINUITY\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let second = playlist.enumerate_segments().nth(1).unwrap();
+    ///
+    /// assert_eq!(second.index(), 1);
+    /// assert_eq!(second.media_sequence(), 8);
+    /// assert_eq!(second.discontinuity_sequence(), 1);
+    /// assert_eq!(second.start(), Duration::from_secs(10));
+    /// assert_eq!(second.segment().uri(), "http://media.example.com/second.ts");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn enumerate_segments(&self) -> impl Iterator<Item = SegmentInfo<'_>> {
+        let media_sequence = self
+            .media_sequence_tag
+            .map_or(0, ExtXMediaSequence::seq_num);
+        let mut start = Duration::from_secs(0);
+
+        self.segments
+            .iter()
+            .zip(self.segment_discontinuity_sequences())
+            .enumerate()
+            .map(move |(index, (segment, discontinuity_sequence))| {
+                let info = SegmentInfo {
+                    index,
+                    // the builder rejects media sequence numbers above `u64::MAX`
+                    media_sequence: media_sequence + index as u64,
+                    discontinuity_sequence,
+                    start,
+                    segment,
+                };
+
+                start += segment.inf_tag().duration();
+                info
+            })
+    }
+
+    /// Returns the media sequence number of every [`MediaSegment`].
+    ///
+    /// The first segment has the number of the [`ExtXMediaSequence`] tag or
+    /// `0`, if the tag is missing, and every following segment increments the
+    /// number.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-MEDIA-SEQUENCE:7\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.segment_sequence_numbers(), vec![7, 8]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn segment_sequence_numbers(&self) -> Vec<u64> {
+        self.enumerate_segments()
+            .map(|info| info.media_sequence())
+            .collect()
+    }
+
+    /// Returns the [`MediaSegment`] with the given media sequence number or
+    /// `None`, if the playlist does not contain it.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-MEDIA-SEQUENCE:7\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(
+    ///     playlist.segment_by_sequence(8).map(|s| s.uri().as_str()),
+    ///     Some("http://media.example.com/second.ts")
+    /// );
+    /// assert_eq!(playlist.segment_by_sequence(6), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn segment_by_sequence(&self, sequence_number: u64) -> Option<&MediaSegment> {
+        let media_sequence = self
+            .media_sequence_tag
+            .map_or(0, ExtXMediaSequence::seq_num);
+
+        sequence_number
+            .checked_sub(media_sequence)
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| self.segments.get(index))
+    }
+
+    /// Returns the [`ExtXMap`], that applies to the [`MediaSegment`] at the
+    /// given index.
+    ///
+    /// An [`ExtXMap`] tag applies to every segment, that follows it, until the
+    /// next [`ExtXMap`] tag (for example after an [`ExtXDiscontinuity`]). This
+    /// function returns `None`, if the index is out of bounds or if no
+    /// [`ExtXMap`] precedes the segment.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-VERSION:6\n\
+    ///                 #EXT-X-MAP:URI=\"init.mp4\"\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.m4s\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.m4s"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(
+    ///     playlist.map_for_segment(1).map(|m| m.uri().as_str()),
+    ///     Some("init.mp4")
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXMap`]: crate::tags::ExtXMap
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    pub fn map_for_segment(&self, index: usize) -> Option<&ExtXMap> {
+        self.segments
+            .get(..=index)?
+            .iter()
+            .rev()
+            .find_map(|s| s.map_tag().as_ref())
+    }
+
+    /// Rounds the duration of every [`MediaSegment`] to the nearest multiple
+    /// of `to`.
+    ///
+    /// # Note
+    /// This is lossy, the original durations can not be restored afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::time::Duration;
+    ///
+    /// let mut playlist = "#EXTM3U\n\
+    ///                     #EXT-X-TARGETDURATION:10\n\
+    ///                     #EXTINF:9.009,\n\
+    ///                     http://media.example.com/first.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// playlist.snap_durations(Duration::from_secs(1))?;
+    ///
+    /// assert_eq!(
+    ///     playlist.segments()[0].inf_tag().duration(),
+    ///     Duration::from_secs(9)
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if `to` is zero or if a snapped duration exceeds
+    /// the [`ExtXTargetDuration`] (see `allowable_excess_duration`). In that
+    /// case the playlist is not modified.
+    pub fn snap_durations(&mut self, to: Duration) -> crate::Result<()> {
+        let step = to.as_nanos();
+
+        if step == 0 {
+            return Err(Error::custom("Can not snap durations to zero"));
+        }
+
+        let durations = self
+            .segments
+            .iter()
+            .map(|s| {
+                let duration = s.inf_tag().duration().as_nanos();
+                Duration::from_nanos((((duration + step / 2) / step) * step) as u64)
+            })
+            .collect::<Vec<_>>();
+
+        for (segment, duration) in self.segments.iter().zip(&durations) {
+            check_segment_duration(
+                segment,
+                *duration,
+                self.target_duration_tag.duration(),
+                self.allowable_excess_duration,
+            )?;
+        }
+
+        for (segment, duration) in self.segments.iter_mut().zip(durations) {
+            let mut inf_tag = segment.inf_tag().clone();
+            inf_tag.set_duration(duration);
+            segment.set_inf_tag(inf_tag);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the total number of frames of all segments at the given frame
+    /// rate.
+    ///
+    /// The frames are counted per segment (see [`MediaSegment::frame_count`])
+    /// and then summed up, so the result matches the segment boundaries.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:4,\n\
+    ///                 http://media.example.com/second.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.total_frames(25.0), 350);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn total_frames(&self, fps: f64) -> u64 {
+        self.segments.iter().map(|s| s.frame_count(fps)).sum()
+    }
+
+    /// Returns the durations of all [`MediaSegment`]s in the order of the
+    /// segments.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:4.5,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(
+    ///     playlist.durations(),
+    ///     vec![Duration::from_secs(10), Duration::from_millis(4500)]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn durations(&self) -> Vec<Duration> {
+        self.segments
+            .iter()
+            .map(|s| s.inf_tag().duration())
+            .collect()
+    }
+
+    /// Returns the durations of all [`MediaSegment`]s in seconds in the order
+    /// of the segments.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXTINF:4.5,\n\
+    ///                 http://media.example.com/second.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.durations_secs_f64(), vec![10.0, 4.5]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn durations_secs_f64(&self) -> Vec<f64> {
+        self.segments
+            .iter()
+            .map(|s| s.inf_tag().duration().as_secs_f64())
+            .collect()
+    }
+
+    /// Returns the indices of all segments, whose [`ExtXProgramDateTime`] tag
+    /// does not advance past the previous [`ExtXProgramDateTime`] tag.
+    ///
+    /// The program date time may jump backwards at an [`ExtXDiscontinuity`]
+    /// tag, so only segments within the same continuous range are compared.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+08:00\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:13.031+08:00\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/second.ts\n\
+    ///                 #EXT-X-DISCONTINUITY\n\
+    ///                 #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:03.031+08:00\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/third.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert_eq!(playlist.program_date_time_anomalies(), vec![1]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXProgramDateTime`]: crate::tags::ExtXProgramDateTime
+    /// [`ExtXDiscontinuity`]: crate::tags::ExtXDiscontinuity
+    pub fn program_date_time_anomalies(&self) -> Vec<usize> {
+        let mut result = vec![];
+        let mut previous = None;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            if segment.discontinuity_tag().is_some() {
+                previous = None;
+            }
+
+            if let Some(tag) = segment.program_date_time_tag() {
+                let date_time = tag.date_time();
+
+                match previous {
+                    Some(previous) if date_time <= previous => result.push(i),
+                    _ => {}
+                }
+
+                previous = Some(date_time);
+            }
+        }
+
+        result
+    }
+
+    /// Returns all [`ExtXDateRange`] tags grouped by their `CLASS` attribute.
+    ///
+    /// Tags without a `CLASS` attribute are grouped under an empty key.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXT-X-DATERANGE:ID=\"ad-1\",CLASS=\"com.apple.hls.interstitial\",\
+    ///                 START-DATE=\"2020-01-02T21:55:44.000Z\"\n\
+    ///                 #EXTINF:10,\n\
+    ///                 http://media.example.com/first.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let groups = playlist.dateranges_by_class();
+    ///
+    /// assert_eq!(groups["com.apple.hls.interstitial"].len(), 1);
+    /// assert_eq!(groups["com.apple.hls.interstitial"][0].id(), "ad-1");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn dateranges_by_class(&self) -> HashMap<String, Vec<&ExtXDateRange>> {
+        let mut result: HashMap<String, Vec<&ExtXDateRange>> = HashMap::new();
+
+        for tag in self
+            .segments
+            .iter()
+            .filter_map(|s| s.date_range_tag().as_ref())
+        {
+            result
+                .entry(tag.class().clone().unwrap_or_default())
+                .or_default()
+                .push(tag);
+        }
+
+        result
+    }
+}
+
+impl RequiredVersion for MediaPlaylist {
+    fn required_version(&self) -> ProtocolVersion {
+        required_version![
+            self.target_duration_tag,
+            self.media_sequence_tag,
+            self.discontinuity_sequence_tag,
+            self.playlist_type_tag,
+            self.i_frames_only_tag,
+            self.independent_segments_tag,
+            self.start_tag,
+            self.end_list_tag,
+            self.segments
+        ]
+    }
+}
+
+impl fmt::Display for MediaPlaylist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_with(f, &WriteOptions::default(), self.required_version())
+    }
+}
+
+impl MediaPlaylist {
+    /// Writes the playlist with the given [`WriteOptions`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::WriteOptions;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:9.009,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let options = WriteOptions {
+    ///     final_newline: false,
+    ///     ..WriteOptions::default()
+    /// };
+    ///
+    /// assert!(playlist.to_string_with(&options).ends_with("#EXT-X-ENDLIST"));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn to_string_with(&self, options: &WriteOptions) -> String {
+        let mut result = String::new();
+        // writing into a `String` can not fail
+        self.write_with(&mut result, options, self.required_version())
+            .unwrap();
+
+        if !options.final_newline && result.ends_with('\n') {
+            result.pop();
+        }
+
+        result
+    }
+
+    /// Returns the playlist as a string, that declares the given
+    /// [`ProtocolVersion`] in its [`ExtXVersion`] tag.
+    ///
+    /// This can be used to make playlists for clients, that only support
+    /// older versions of the protocol.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::types::ProtocolVersion;
+    ///
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:9.009,\n\
+    ///                 http://media.example.com/first.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// assert!(playlist
+    ///     .emit_for_version(ProtocolVersion::V3)?
+    ///     .contains("#EXT-X-VERSION:3"));
+    ///
+    /// assert!(playlist.emit_for_version(ProtocolVersion::V2).is_err());
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if the playlist uses features, that require a
+    /// higher [`ProtocolVersion`] than `version`.
+    pub fn emit_for_version(&self, version: ProtocolVersion) -> crate::Result<String> {
+        let required_version = self.required_version();
+
+        if required_version > version {
+            return Err(Error::custom(format!(
+                "The playlist requires version {}, but version {} was requested",
+                required_version, version
+            )));
+        }
+
+        let options = WriteOptions {
+            always_emit_version: true,
+            ..WriteOptions::default()
+        };
+
+        let mut result = String::new();
+        // writing into a `String` can not fail
+        self.write_with(&mut result, &options, version).unwrap();
+
+        Ok(result)
+    }
+
+    /// Writes the playlist into an [`io::Write`].
+    ///
+    /// Unlike writing the playlist through its [`Display`] implementation,
+    /// this function returns the actual [`io::Error`] (for example because the
+    /// disk is full or the pipe has been closed) instead of an opaque
+    /// [`fmt::Error`].
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let playlist = "#EXTM3U\n\
+    ///                 #EXT-X-TARGETDURATION:10\n\
+    ///                 #EXTINF:9.009,\n\
+    ///                 http://media.example.com/first.ts\n\
+    ///                 #EXT-X-ENDLIST"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let mut output = vec![];
+    /// playlist.write_io(&mut output)?;
+    ///
+    /// assert_eq!(output, playlist.to_string().into_bytes());
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if writing into `writer` fails.
+    ///
+    /// [`Display`]: std::fmt::Display
+    pub fn write_io<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut adapter = IoWriter {
+            inner: writer,
+            error: None,
+        };
+
+        self.write_with(
+            &mut adapter,
+            &WriteOptions::default(),
+            self.required_version(),
+        )
+        .map_err(|_| {
+            adapter
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "formatter error"))
+        })
+    }
+
+    fn write_with<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        options: &WriteOptions,
+        version: ProtocolVersion,
+    ) -> fmt::Result {
+        writeln!(f, "{}", ExtM3u)?;
+        if options.always_emit_version || version != ProtocolVersion::V1 {
+            writeln!(f, "{}", ExtXVersion::new(version))?;
+        }
+        writeln!(f, "{}", self.target_duration_tag())?;
+        if let Some(value) = &self.media_sequence_tag {
+            writeln!(f, "{}", value)?;
+        }
+        if let Some(value) = &self.discontinuity_sequence_tag {
+            writeln!(f, "{}", value)?;
+        }
+        if let Some(value) = &self.playlist_type_tag {
+            writeln!(f, "{}", value)?;
+        }
+        if let Some(value) = &self.i_frames_only_tag {
+            writeln!(f, "{}", value)?;
+        }
+        if let Some(value) = &self.independent_segments_tag {
+            writeln!(f, "{}", value)?;
+        }
+        if let Some(value) = &self.start_tag {
+            writeln!(f, "{}", value)?;
+        }
+        // ExtXKey and ExtXBitrate tags apply to all following segments, so
+        // they only have to be written, if they change.
+        let mut previous = None;
+        for segment in &self.segments {
+            segment.write_with(f, previous)?;
+            previous = Some(segment);
+        }
+        if let Some(value) = &self.end_list_tag {
+            writeln!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks, that the rounded `duration` of the `segment` does not exceed the
+/// `target_duration` by more than the `allowable_excess_duration`.
+fn check_segment_duration(
+    segment: &MediaSegment,
+    duration: Duration,
+    target_duration: Duration,
+    allowable_excess_duration: Duration,
+) -> crate::Result<()> {
+    let max_segment_duration = target_duration + allowable_excess_duration;
+
+    if round_duration(duration) > max_segment_duration {
+        return Err(Error::custom(format!(
+            "Too large segment duration: actual={:?}, max={:?}, target_duration={:?}, \
+             uri={:?}",
+            duration,
+            max_segment_duration,
+            target_duration,
+            segment.uri()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Replaces the variable references in the playlist with the values of the
+/// variables, defined by the preceding `EXT-X-DEFINE` tags.
+fn substitute_variables(
+    input: &str,
+    query_params: &HashMap<String, String>,
+) -> crate::Result<String> {
+    const PREFIX: &str = "#EXT-X-DEFINE:";
+
+    let mut variables: HashMap<String, String> = HashMap::new();
+    let mut result = String::with_capacity(input.len());
+
+    for line in input.lines() {
+        let line = {
+            if line.trim_start().starts_with("#EXT") {
+                // only the attributes of a tag can contain variable references
+                let (tag, attributes) = line.split_at(line.find(':').unwrap_or(line.len()));
+                format!("{}{}", tag, replace_references(attributes, &variables)?)
+            } else if line.trim_start().starts_with('#') {
+                // comments are not modified
+                line.to_string()
+            } else {
+                replace_references(line, &variables)?
+            }
+        };
+
+        if line.trim().starts_with(PREFIX) {
+            let mut name = None;
+            let mut value = None;
+
+            for (key, attribute) in crate::parse_attributes(&line)? {
+                match key.as_str() {
+                    "NAME" => name = Some(attribute),
+                    "VALUE" => value = Some(attribute),
+                    "QUERYPARAM" => {
+                        value = Some(query_params.get(&attribute).cloned().ok_or_else(|| {
+                            Error::custom(format!("Undefined query parameter: {:?}", attribute))
+                        })?);
+                        name = Some(attribute);
+                    }
+                    _ => {}
+                }
+            }
+
+            let name = name.ok_or_else(|| Error::missing_value("NAME"))?;
+            let value = value.ok_or_else(|| Error::missing_value("VALUE"))?;
+
+            if variables.contains_key(&name) {
+                return Err(Error::custom(format!(
+                    "The variable {:?} is defined more than once",
+                    name
+                )));
+            }
+
+            variables.insert(name, value);
+        }
+
+        result.push_str(&line);
+        result.push('\n');
+    }
+
+    Ok(result)
+}
+
+/// Replaces every variable reference (`{$name}`) in `input` from left to
+/// right. The inserted values are not searched for references.
+fn replace_references(input: &str, variables: &HashMap<String, String>) -> crate::Result<String> {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{$") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let end = rest.find('}').unwrap_or(rest.len());
+        let name = &rest[..end];
+        let value = variables
+            .get(name)
+            .ok_or_else(|| Error::custom(format!("Undefined variable: {:?}", name)))?;
+
+        result.push_str(value);
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn parse_media_playlist(
+    input: &str,
+    builder: &mut MediaPlaylistBuilder,
+    on_segment: &mut dyn FnMut(usize),
+    lints: &mut Vec<Lint>,
+) -> crate::Result<MediaPlaylist> {
+    let mut segment = MediaSegment::builder();
+    let mut segments = vec![];
+    let mut unknown_tags = vec![];
+
+    let mut has_partial_segment = false;
+    let mut has_discontinuity_tag = false;
+    let mut has_inf_tag = false;
+    let mut has_map_tag = false;
+    let mut has_redundant_key = false;
+
+    let mut available_key_tags: Vec<crate::tags::ExtXKey> = vec![];
+    // An ExtXBitrate applies to every MediaSegment between it and the next
+    // ExtXBitrate tag (or the end of the playlist).
+    let mut bitrate_tag = None;
+
+    for (i, line) in input.parse::<Lines>()?.into_iter().enumerate() {
+        match line {
+            Line::Tag(tag) => {
+                if i == 0 {
+                    if tag != Tag::ExtM3u(ExtM3u) {
+                        return Err(Error::custom("m3u8 doesn't start with #EXTM3U"));
+                    }
+                    continue;
                 }
                 match tag {
                     Tag::ExtM3u(_) => return Err(Error::invalid_input()),
                     Tag::ExtInf(t) => {
                         has_partial_segment = true;
+                        has_inf_tag = true;
                         segment.inf_tag(t);
                     }
                     Tag::ExtXByteRange(t) => {
                         has_partial_segment = true;
                         segment.byte_range_tag(t);
                     }
+                    Tag::ExtXBitrate(t) => {
+                        has_partial_segment = true;
+                        bitrate_tag = Some(t);
+                    }
                     Tag::ExtXDiscontinuity(t) => {
                         has_discontinuity_tag = true;
                         has_partial_segment = true;
@@ -274,27 +1814,31 @@ fn parse_media_playlist(
                     }
                     Tag::ExtXKey(t) => {
                         has_partial_segment = true;
-                        if available_key_tags.is_empty() {
-                            // An ExtXKey applies to every MediaSegment and to every Media
-                            // Initialization Section declared by an EXT-X-MAP tag, that appears
-                            // between it and the next EXT-X-KEY tag in the Playlist file with the
-                            // same KEYFORMAT attribute (or the end of the Playlist file).
-                            available_key_tags = available_key_tags
-                                .into_iter()
-                                .map(|k| {
-                                    if t.key_format() == k.key_format() {
-                                        t.clone()
-                                    } else {
-                                        k
-                                    }
-                                })
-                                .collect();
+                        // An ExtXKey applies to every MediaSegment and to every Media
+                        // Initialization Section declared by an EXT-X-MAP tag, that appears
+                        // between it and the next EXT-X-KEY tag in the Playlist file with the
+                        // same KEYFORMAT attribute (or the end of the Playlist file).
+                        //
+                        // A METHOD of NONE means that the following segments are not
+                        // encrypted at all, so it removes every key in effect.
+                        if available_key_tags.contains(&t) {
+                            has_redundant_key = true;
+                        }
+
+                        if t.is_empty() {
+                            available_key_tags.clear();
+                        } else if let Some(key) = available_key_tags
+                            .iter_mut()
+                            .find(|k| k.key_format() == t.key_format())
+                        {
+                            *key = t;
                         } else {
                             available_key_tags.push(t);
                         }
                     }
                     Tag::ExtXMap(mut t) => {
                         has_partial_segment = true;
+                        has_map_tag = true;
 
                         t.set_keys(available_key_tags.clone());
                         segment.map_tag(t);
@@ -314,7 +1858,8 @@ fn parse_media_playlist(
                         builder.media_sequence_tag(t);
                     }
                     Tag::ExtXDiscontinuitySequence(t) => {
-                        if segments.is_empty() {
+                        // has to appear before the first MediaSegment
+                        if !segments.is_empty() || has_partial_segment {
                             return Err(Error::invalid_input());
                         }
                         if has_discontinuity_tag {
@@ -335,7 +1880,8 @@ fn parse_media_playlist(
                     | Tag::ExtXStreamInf(_)
                     | Tag::ExtXIFrameStreamInf(_)
                     | Tag::ExtXSessionData(_)
-                    | Tag::ExtXSessionKey(_) => {
+                    | Tag::ExtXSessionKey(_)
+                    | Tag::ExtXContentSteering(_) => {
                         return Err(Error::unexpected_tag(tag));
                     }
                     Tag::ExtXIndependentSegments(t) => {
@@ -344,41 +1890,88 @@ fn parse_media_playlist(
                     Tag::ExtXStart(t) => {
                         builder.start_tag(t);
                     }
-                    Tag::Unknown(_) | Tag::ExtXVersion(_) => {
+                    Tag::UnsupportedVersion(version) => {
+                        if builder.reject_unsupported_version.unwrap_or(false) {
+                            return Err(Error::unknown_protocol_version(version));
+                        }
+                    }
+                    Tag::ExtXVersion(_) => {
+                        // This tag can be ignored, because the MediaPlaylist
+                        // will automatically set the ExtXVersion tag to the
+                        // correct version!
+                    }
+                    Tag::Unknown(t) => {
                         // [6.3.1. General Client Responsibilities]
                         // > ignore any unrecognized tags.
+                        unknown_tags.push(t);
                     }
                 }
             }
             Line::Uri(uri) => {
+                if !has_inf_tag {
+                    return Err(Error::custom(format!(
+                        "segment URI without preceding EXTINF: {:?}",
+                        uri
+                    )));
+                }
                 segment.uri(uri);
                 segment.keys(available_key_tags.clone());
+                if let Some(t) = bitrate_tag {
+                    segment.bitrate_tag(t);
+                }
+                if has_redundant_key {
+                    lints.push(Lint::new(
+                        LintCode::RedundantKey,
+                        format!(
+                            "The segment {} is preceded by an EXT-X-KEY, that is equal to the \
+                             key in effect",
+                            segments.len()
+                        ),
+                    ));
+                }
                 segments.push(segment.build().map_err(Error::builder_error)?);
+                on_segment(segments.len());
                 segment = MediaSegment::builder();
                 has_partial_segment = false;
+                has_inf_tag = false;
+                has_map_tag = false;
+                has_redundant_key = false;
             }
         }
     }
 
+    // An ExtXMap, that is not followed by any media segment, does not apply
+    // to anything, which is most likely a packaging error.
+    if has_map_tag && builder.strict.unwrap_or(false) {
+        return Err(Error::custom(
+            "EXT-X-MAP is not followed by a media segment",
+        ));
+    }
+
     if has_partial_segment {
         return Err(Error::invalid_input());
     }
 
     builder.segments(segments);
-    builder.build().map_err(Error::builder_error)
+
+    let mut playlist = builder.build().map_err(Error::builder_error)?;
+    playlist.unknown_tags = unknown_tags;
+
+    Ok(playlist)
 }
 
 impl FromStr for MediaPlaylist {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        parse_media_playlist(input, &mut Self::builder())
+        parse_media_playlist(input, &mut Self::builder(), &mut |_| {}, &mut vec![])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tags::{ExtInf, ExtXBitrate, ExtXDiscontinuity, ExtXKey, ExtXProgramDateTime};
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -412,8 +2005,1586 @@ mod tests {
     }
 
     #[test]
-    fn test_empty_playlist() {
-        let playlist = "";
-        assert!(playlist.parse::<MediaPlaylist>().is_err());
+    fn test_bitrate_inheritance() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-BITRATE:4500\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXTINF:3.003,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.segments().len(), 3);
+        for segment in playlist.segments() {
+            assert_eq!(segment.bitrate_tag(), Some(ExtXBitrate::new(4500)));
+            assert_eq!(segment.bitrate(), Some(4500));
+        }
+    }
+
+    #[test]
+    fn test_uri_without_ext_inf() {
+        let error = "#EXTM3U\n\
+                     #EXT-X-TARGETDURATION:10\n\
+                     http://media.example.com/first.ts\n\
+                     #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "CustomError: segment URI without preceding EXTINF: \"http://media.example.com/first.ts\""
+        );
+    }
+
+    #[test]
+    fn test_startup_buffer() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9.5,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:9.5,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.startup_buffer(0), Duration::from_secs(0));
+        assert_eq!(playlist.startup_buffer(1), Duration::from_millis(9500));
+        assert_eq!(playlist.startup_buffer(2), Duration::from_secs(19));
+        assert_eq!(playlist.startup_buffer(10), Duration::from_secs(23));
+
+        // the playback of a live playlist starts three target durations (12s)
+        // before the end, which is the fourth segment:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-MEDIA-SEQUENCE:10\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/10.ts\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/11.ts\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/12.ts\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/13.ts\n\
+                        #EXTINF:3.5,\n\
+                        http://media.example.com/14.ts\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/15.ts\n\
+                        #EXTINF:2,\n\
+                        http://media.example.com/16.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.startup_buffer(1), Duration::from_secs(4));
+        assert_eq!(playlist.startup_buffer(2), Duration::from_millis(7500));
+        assert_eq!(playlist.startup_buffer(10), Duration::from_millis(13500));
+    }
+
+    #[test]
+    fn test_unknown_tags() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-NEW-TAG:FOO=BAR,BAZ=\"qux\"\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ANOTHER-TAG\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.unknown_tags(),
+            &[
+                "#EXT-X-NEW-TAG:FOO=BAR,BAZ=\"qux\"".to_string(),
+                "#EXT-X-ANOTHER-TAG".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uri_whitespace() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9.009,\n   \
+                        http://media.example.com/first.ts   \n\
+                        #EXT-X-ENDLIST";
+
+        // surrounding whitespace is trimmed:
+        assert_eq!(
+            playlist.parse::<MediaPlaylist>().unwrap().segments()[0].uri(),
+            "http://media.example.com/first.ts"
+        );
+        MediaPlaylist::builder()
+            .strict(true)
+            .parse(playlist)
+            .unwrap();
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first segment.ts\n\
+                        #EXT-X-ENDLIST";
+
+        assert!(playlist.parse::<MediaPlaylist>().is_ok());
+        assert!(MediaPlaylist::builder()
+            .strict(true)
+            .parse(playlist)
+            .is_err());
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/my key.bin\"\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST";
+
+        assert!(playlist.parse::<MediaPlaylist>().is_ok());
+        assert_eq!(
+            MediaPlaylist::builder()
+                .strict(true)
+                .parse(playlist)
+                .unwrap_err()
+                .to_string(),
+            "BuilderError: CustomError: URI contains whitespace: \
+             \"https://example.com/my key.bin\""
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_percent_encoded_uri() {
+        let input = "#EXTM3U\n\
+                     #EXT-X-VERSION:6\n\
+                     #EXT-X-TARGETDURATION:10\n\
+                     #EXT-X-MAP:URI=\"init%20section.mp4\"\n\
+                     #EXTINF:9.009,\n\
+                     http://media.example.com/first%20segment.ts?a=b%2Cc\n\
+                     #EXT-X-ENDLIST\n";
+
+        let playlist = MediaPlaylist::builder().strict(true).parse(input).unwrap();
+
+        assert_eq!(
+            playlist.segments()[0].uri(),
+            "http://media.example.com/first%20segment.ts?a=b%2Cc"
+        );
+        assert_eq!(
+            playlist.segments()[0].map_tag().as_ref().unwrap().uri(),
+            "init%20section.mp4"
+        );
+        assert_eq!(playlist.to_string(), input.to_string());
+        // the option of the builder is not part of the playlist:
+        assert_eq!(playlist, input.parse::<MediaPlaylist>().unwrap());
+    }
+
+    #[test]
+    fn test_uri_verbatim() {
+        // only lines, that start with a `#` are tags or comments, everything
+        // else is a URI, that is kept as it is:
+        let input = "#EXTM3U\n\
+                     #EXT-X-TARGETDURATION:10\n\
+                     #EXTINF:10,\n\
+                     http://media.example.com/first.ts?token=a%23b&c=d\n\
+                     #EXTINF:10,\n\
+                     ../second.ts?q=1#fragment\n\
+                     # a comment\n\
+                     #EXTINF:10,\n\
+                     \t  /third segment.ts  \n\
+                     #EXT-X-ENDLIST\n";
+
+        let playlist = input.parse::<MediaPlaylist>().unwrap();
+
+        assert_eq!(
+            playlist
+                .segments()
+                .iter()
+                .map(|s| s.uri().as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "http://media.example.com/first.ts?token=a%23b&c=d",
+                "../second.ts?q=1#fragment",
+                "/third segment.ts",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_playlist() {
+        let playlist = "";
+        assert!(playlist.parse::<MediaPlaylist>().is_err());
+    }
+
+    #[test]
+    fn test_write_io() {
+        /// A writer, that fails after `limit` bytes.
+        struct LimitedWriter {
+            written: Vec<u8>,
+            limit: usize,
+        }
+
+        impl io::Write for LimitedWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let available = self.limit - self.written.len();
+
+                if available == 0 {
+                    return Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"));
+                }
+
+                let len = buf.len().min(available);
+                self.written.extend_from_slice(&buf[..len]);
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> io::Result<()> { Ok(()) }
+        }
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let mut writer = LimitedWriter {
+            written: vec![],
+            limit: 20,
+        };
+
+        let error = playlist.write_io(&mut writer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(error.to_string(), "broken pipe".to_string());
+        assert_eq!(writer.written, b"#EXTM3U\n#EXT-X-TARGE".to_vec());
+
+        let mut writer = LimitedWriter {
+            written: vec![],
+            limit: 1000,
+        };
+
+        playlist.write_io(&mut writer).unwrap();
+        assert_eq!(writer.written, playlist.to_string().into_bytes());
+    }
+
+    #[test]
+    fn test_map_for_segment() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-VERSION:6\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/0.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXT-X-MAP:URI=\"main/init.mp4\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/main/1.m4s\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/main/2.m4s\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXT-X-MAP:URI=\"ad/init.mp4\",BYTERANGE=\"720@0\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/ad/3.m4s\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/ad/4.m4s\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            (0..6)
+                .map(|i| playlist.map_for_segment(i).map(|m| m.uri().as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                None,
+                Some("main/init.mp4"),
+                Some("main/init.mp4"),
+                Some("ad/init.mp4"),
+                Some("ad/init.mp4"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_media_sequence() {
+        let playlist = format!(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-MEDIA-SEQUENCE:{}\n\
+             #EXTINF:10,\n\
+             http://media.example.com/first.ts\n\
+             #EXTINF:10,\n\
+             http://media.example.com/second.ts\n\
+             #EXTINF:10,\n\
+             http://media.example.com/third.ts\n",
+            u64::MAX - 2
+        )
+        .parse::<MediaPlaylist>()
+        .unwrap();
+
+        assert_eq!(
+            playlist.segment_sequence_numbers(),
+            vec![u64::MAX - 2, u64::MAX - 1, u64::MAX]
+        );
+        assert_eq!(
+            playlist
+                .segment_by_sequence(u64::MAX)
+                .map(|s| s.uri().as_str()),
+            Some("http://media.example.com/third.ts")
+        );
+        assert_eq!(
+            playlist
+                .segment_by_sequence(u64::MAX - 2)
+                .map(|s| s.uri().as_str()),
+            Some("http://media.example.com/first.ts")
+        );
+        assert_eq!(playlist.segment_by_sequence(u64::MAX - 3), None);
+        assert_eq!(playlist.segment_by_sequence(0), None);
+
+        // the media sequence number of the third segment would overflow:
+        assert!(format!(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-MEDIA-SEQUENCE:{}\n\
+             #EXTINF:10,\n\
+             http://media.example.com/first.ts\n\
+             #EXTINF:10,\n\
+             http://media.example.com/second.ts\n\
+             #EXTINF:10,\n\
+             http://media.example.com/third.ts\n",
+            u64::MAX - 1
+        )
+        .parse::<MediaPlaylist>()
+        .is_err());
+    }
+
+    #[test]
+    fn test_durations() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-VERSION:3\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXTINF:3.003,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.durations(),
+            vec![
+                Duration::from_millis(9009),
+                Duration::from_millis(9009),
+                Duration::from_millis(3003),
+            ]
+        );
+        assert_eq!(playlist.durations_secs_f64(), vec![9.009, 9.009, 3.003]);
+    }
+
+    #[test]
+    fn test_i_frames_only_position() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        #EXT-X-BYTERANGE:9400@376\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-I-FRAMES-ONLY\n\
+                        #EXTINF:10,\n\
+                        #EXT-X-BYTERANGE:7144@1316\n\
+                        http://media.example.com/second.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.i_frames_only_tag(), Some(ExtXIFramesOnly));
+        assert_eq!(playlist.segments().len(), 2);
+        assert_eq!(playlist.required_version(), ProtocolVersion::V4);
+        assert_eq!(
+            playlist.to_string(),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:4\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-I-FRAMES-ONLY\n\
+             #EXT-X-BYTERANGE:9400@376\n\
+             #EXTINF:10,\n\
+             http://media.example.com/first.ts\n\
+             #EXT-X-BYTERANGE:7144@1316\n\
+             #EXTINF:10,\n\
+             http://media.example.com/second.ts\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_is_initializing() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-VERSION:3\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-MEDIA-SEQUENCE:0\n\
+                        #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,HOLD-BACK=12.0\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert!(playlist.segments().is_empty());
+        assert!(playlist.is_initializing());
+
+        // the first segment has been published:
+        assert!(!"#EXTM3U\n\
+                  #EXT-X-TARGETDURATION:4\n\
+                  #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,HOLD-BACK=12.0\n\
+                  #EXTINF:4,\n\
+                  segment0.mp4\n"
+            .parse::<MediaPlaylist>()
+            .unwrap()
+            .is_initializing());
+
+        // no server control:
+        assert!(!"#EXTM3U\n\
+                  #EXT-X-TARGETDURATION:4\n"
+            .parse::<MediaPlaylist>()
+            .unwrap()
+            .is_initializing());
+
+        // not a live playlist:
+        assert!(!"#EXTM3U\n\
+                  #EXT-X-TARGETDURATION:4\n\
+                  #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,HOLD-BACK=12.0\n\
+                  #EXT-X-ENDLIST\n"
+            .parse::<MediaPlaylist>()
+            .unwrap()
+            .is_initializing());
+    }
+
+    #[test]
+    fn test_is_low_latency() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:8\n\
+                        #EXT-X-MEDIA-SEQUENCE:2680\n\
+                        #EXTINF:7.975,\n\
+                        https://priv.example.com/fileSequence2680.ts\n\
+                        #EXTINF:7.941,\n\
+                        https://priv.example.com/fileSequence2681.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+        assert!(!playlist.is_low_latency());
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,PART-HOLD-BACK=1.0\n\
+                        #EXTINF:4.00008,\n\
+                        fileSequence268.mp4"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+        assert!(playlist.is_low_latency());
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=NO,CAN-SKIP-UNTIL=24.0\n\
+                        #EXTINF:4.00008,\n\
+                        fileSequence268.mp4"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+        assert!(!playlist.is_low_latency());
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-PART-INF:PART-TARGET=0.33334\n\
+                        #EXTINF:4.00008,\n\
+                        fileSequence268.mp4"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+        assert!(playlist.is_low_latency());
+    }
+
+    #[test]
+    fn test_final_newline() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let expected = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST";
+
+        assert_eq!(playlist.to_string(), format!("{}\n", expected));
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions::default()),
+            format!("{}\n", expected)
+        );
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions {
+                final_newline: false,
+                ..WriteOptions::default()
+            }),
+            expected.to_string()
+        );
+    }
+
+    #[test]
+    fn test_bitrate_output() {
+        let input = "#EXTM3U\n\
+                     #EXT-X-TARGETDURATION:10\n\
+                     #EXT-X-BITRATE:4500\n\
+                     #EXTINF:9,\n\
+                     http://media.example.com/first.ts\n\
+                     #EXTINF:9,\n\
+                     http://media.example.com/second.ts\n\
+                     #EXTINF:9,\n\
+                     http://media.example.com/third.ts\n\
+                     #EXT-X-BITRATE:1200\n\
+                     #EXTINF:9,\n\
+                     http://media.example.com/fourth.ts\n\
+                     #EXT-X-ENDLIST\n";
+
+        let playlist = input.parse::<MediaPlaylist>().unwrap();
+
+        assert_eq!(playlist.to_string(), input.to_string());
+        assert_eq!(
+            playlist.to_string().matches("#EXT-X-BITRATE:4500").count(),
+            1
+        );
+        assert_eq!(
+            playlist.to_string().parse::<MediaPlaylist>().unwrap(),
+            playlist
+        );
+    }
+
+    #[test]
+    fn test_bitrate_removal() {
+        let segment = |bitrate: Option<u64>, uri: &str| {
+            let mut builder = MediaSegment::builder();
+            builder
+                .inf_tag(ExtInf::new(Duration::from_secs(9)))
+                .uri(uri);
+
+            if let Some(bitrate) = bitrate {
+                builder.bitrate_tag(ExtXBitrate::new(bitrate));
+            }
+
+            builder.build().unwrap()
+        };
+
+        let playlist = MediaPlaylist::builder()
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .segments(vec![
+                segment(Some(1000), "http://media.example.com/first.ts"),
+                segment(Some(1200), "http://media.example.com/second.ts"),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            playlist.to_string().parse::<MediaPlaylist>().unwrap(),
+            playlist
+        );
+
+        // the bitrate of the first segment would be inherited by the second one:
+        assert!(MediaPlaylist::builder()
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .segments(vec![
+                segment(Some(1000), "http://media.example.com/first.ts"),
+                segment(None, "http://media.example.com/second.ts"),
+            ])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_program_date_time_anomalies() {
+        // a backward jump after a discontinuity is legal:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:33.031+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:50:00.000+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:50:10.000+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/fourth.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.program_date_time_anomalies(), Vec::<usize>::new());
+
+        // a backward jump within a continuous range is not:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:50:00.000+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:50:00.000+08:00\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/fourth.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.program_date_time_anomalies(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_dateranges_by_class() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-DATERANGE:ID=\"ad-1\",CLASS=\"com.apple.hls.interstitial\",\
+                        START-DATE=\"2020-01-02T21:55:44.000Z\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-DATERANGE:ID=\"chapter-1\",CLASS=\"chapter\",\
+                        START-DATE=\"2020-01-02T21:55:54.000Z\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXT-X-DATERANGE:ID=\"ad-2\",CLASS=\"com.apple.hls.interstitial\",\
+                        START-DATE=\"2020-01-02T21:56:04.000Z\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-DATERANGE:ID=\"splice\",START-DATE=\"2020-01-02T21:56:14.000Z\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/fourth.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let groups = playlist.dateranges_by_class();
+        let ids = |class: &str| {
+            groups[class]
+                .iter()
+                .map(|t| t.id().as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(ids("com.apple.hls.interstitial"), vec!["ad-1", "ad-2"]);
+        assert_eq!(ids("chapter"), vec!["chapter-1"]);
+        assert_eq!(ids(""), vec!["splice"]);
+    }
+
+    #[test]
+    fn test_total_frames() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10.01,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:10.01,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXTINF:4.004,\n\
+                        http://media.example.com/third.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.total_frames(23.976), 240 + 240 + 96);
+        assert_eq!(playlist.total_frames(25.0), 250 + 250 + 100);
+    }
+
+    #[test]
+    fn test_orphan_map() {
+        let input = "#EXTM3U\n\
+                     #EXT-X-TARGETDURATION:10\n\
+                     #EXT-X-MAP:URI=\"init.mp4\"\n\
+                     #EXTINF:10,\n\
+                     http://media.example.com/first.mp4\n\
+                     #EXT-X-MAP:URI=\"orphan.mp4\"\n";
+
+        // the trailing map is an incomplete segment:
+        assert!(input.parse::<MediaPlaylist>().is_err());
+
+        // strict mode reports the reason:
+        assert_eq!(
+            MediaPlaylist::builder()
+                .strict(true)
+                .parse(input)
+                .unwrap_err()
+                .to_string(),
+            "CustomError: EXT-X-MAP is not followed by a media segment".to_string()
+        );
+
+        // a map, that is followed by a segment, is fine in strict mode:
+        MediaPlaylist::builder()
+            .strict(true)
+            .parse(
+                "#EXTM3U\n\
+                 #EXT-X-TARGETDURATION:10\n\
+                 #EXT-X-MAP:URI=\"init.mp4\"\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/first.mp4\n",
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_lint() {
+        // a well-formed playlist:
+        assert_eq!(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-PLAYLIST-TYPE:VOD\n\
+             #EXTINF:9.5,\n\
+             http://media.example.com/first.ts\n\
+             #EXT-X-ENDLIST"
+                .parse::<MediaPlaylist>()
+                .unwrap()
+                .lint(),
+            vec![]
+        );
+
+        // the example playlist of RFC 8216 (section 8.2):
+        assert_eq!(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-VERSION:3\n\
+             #EXT-X-PLAYLIST-TYPE:VOD\n\
+             #EXTINF:9.009,\n\
+             http://media.example.com/first.ts\n\
+             #EXTINF:9.009,\n\
+             http://media.example.com/second.ts\n\
+             #EXTINF:3.003,\n\
+             http://media.example.com/third.ts\n\
+             #EXT-X-ENDLIST"
+                .parse::<MediaPlaylist>()
+                .unwrap()
+                .lint(),
+            vec![]
+        );
+
+        let codes =
+            |playlist: &MediaPlaylist| playlist.lint().iter().map(|l| l.code()).collect::<Vec<_>>();
+
+        // the target duration of a playlist, that will not change, is never
+        // reloaded:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:6,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(codes(&playlist), vec![LintCode::MissingPlaylistType]);
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:6,\n\
+                        http://media.example.com/first.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(codes(&playlist), vec![LintCode::LargeTargetDuration]);
+        assert_eq!(
+            playlist.lint()[0].message(),
+            "The target duration (10s) is larger than the longest rounded segment duration (6s)"
+        );
+
+        // a target duration, that is at most one second larger, is fine:
+        assert_eq!(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:9,\n\
+             http://media.example.com/first.ts"
+                .parse::<MediaPlaylist>()
+                .unwrap()
+                .lint(),
+            vec![]
+        );
+
+        let playlist = MediaPlaylist::builder()
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .segments(vec![MediaSegment::builder()
+                .inf_tag(ExtInf::new(Duration::from_secs(10)))
+                .keys(vec![
+                    ExtXKey::new(EncryptionMethod::Aes128, "https://example.com/1.bin"),
+                    ExtXKey::new(EncryptionMethod::Aes128, "https://example.com/2.bin"),
+                ])
+                .uri("http://media.example.com/first.ts")
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+
+        assert_eq!(codes(&playlist), vec![LintCode::RedundantKey]);
+
+        // a key, that is repeated without any change:
+        let (playlist, lints) = MediaPlaylist::builder()
+            .parse_with_lints(
+                "#EXTM3U\n\
+                 #EXT-X-TARGETDURATION:10\n\
+                 #EXT-X-PLAYLIST-TYPE:VOD\n\
+                 #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/first.ts\n\
+                 #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/second.ts\n\
+                 #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/third.ts\n\
+                 #EXT-X-ENDLIST",
+            )
+            .unwrap();
+
+        // the repetition is not part of the playlist:
+        assert_eq!(codes(&playlist), vec![]);
+        assert_eq!(
+            lints.iter().map(|l| l.code()).collect::<Vec<_>>(),
+            vec![LintCode::RedundantKey, LintCode::RedundantKey]
+        );
+        assert_eq!(
+            lints[0].message(),
+            "The segment 1 is preceded by an EXT-X-KEY, that is equal to the key in effect"
+        );
+    }
+
+    #[test]
+    fn test_encryption_coverage() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/0.ts\n\
+                        #EXT-X-KEY:METHOD=AES-128,URI=\"https://priv.example.com/key.php?r=52\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/1.ts\n\
+                        #EXT-X-KEY:METHOD=NONE\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/2.ts\n\
+                        #EXT-X-KEY:METHOD=SAMPLE-AES,URI=\"https://priv.example.com/key.bin\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/3.ts\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/4.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let coverage = playlist.encryption_coverage();
+
+        assert_eq!(coverage.encrypted_segments(), 3);
+        assert_eq!(coverage.clear_segments(), 2);
+        assert_eq!(
+            coverage.methods().iter().cloned().collect::<Vec<_>>(),
+            vec![EncryptionMethod::Aes128, EncryptionMethod::SampleAes]
+        );
+        assert!(coverage.has_clear_gap());
+        assert!(!coverage.is_fully_encrypted());
+
+        // the keys are only written, if they change:
+        assert_eq!(playlist.to_string().matches("#EXT-X-KEY").count(), 3);
+        assert_eq!(
+            playlist.to_string().parse::<MediaPlaylist>().unwrap(),
+            playlist
+        );
+
+        // leading clear segments are not a gap:
+        let coverage = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/0.ts\n\
+                        #EXT-X-KEY:METHOD=AES-128,URI=\"https://priv.example.com/key.php?r=52\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/1.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap()
+            .encryption_coverage();
+
+        assert!(!coverage.has_clear_gap());
+    }
+
+    #[test]
+    fn test_from_builders() {
+        let mut first = MediaSegment::builder();
+        first
+            .inf_tag(ExtInf::new(Duration::from_secs(10)))
+            .uri("http://media.example.com/first.ts");
+
+        let mut second = MediaSegment::builder();
+        second.inf_tag(ExtInf::new(Duration::from_secs(10)));
+
+        assert_eq!(
+            MediaPlaylist::from_builders(Duration::from_secs(10), vec![first.clone(), second])
+                .unwrap_err()
+                .to_string(),
+            "BuilderError: `uri` must be initialized".to_string()
+        );
+
+        let playlist =
+            MediaPlaylist::from_builders(Duration::from_secs(10), vec![first.clone(), first])
+                .unwrap();
+
+        assert_eq!(playlist.segments().len(), 2);
+        assert_eq!(
+            playlist.target_duration_tag(),
+            ExtXTargetDuration::new(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn test_always_emit_version() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions::default()),
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:9,\n\
+             http://media.example.com/first.ts\n\
+             #EXT-X-ENDLIST\n"
+                .to_string()
+        );
+
+        assert_eq!(
+            playlist.to_string_with(&WriteOptions {
+                always_emit_version: true,
+                ..WriteOptions::default()
+            }),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:1\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:9,\n\
+             http://media.example.com/first.ts\n\
+             #EXT-X-ENDLIST\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_reject_unsupported_version() {
+        let playlist = |version: &str| {
+            format!(
+                "#EXTM3U\n\
+                 #EXT-X-VERSION:{}\n\
+                 #EXT-X-TARGETDURATION:10\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/first.ts\n",
+                version
+            )
+        };
+
+        // a supported version:
+        assert_eq!(
+            MediaPlaylist::builder()
+                .reject_unsupported_version(true)
+                .parse(&playlist("3"))
+                .unwrap(),
+            playlist("3").parse::<MediaPlaylist>().unwrap()
+        );
+
+        // an unsupported version is accepted by default:
+        let parsed = playlist("99").parse::<MediaPlaylist>().unwrap();
+        assert_eq!(parsed.segments().len(), 1);
+        assert!(!parsed.to_string().contains("#EXT-X-VERSION:99"));
+
+        assert_eq!(
+            MediaPlaylist::builder()
+                .reject_unsupported_version(true)
+                .parse(&playlist("99"))
+                .unwrap_err()
+                .to_string(),
+            "Unknown Protocol version: \"99\"".to_string()
+        );
+
+        // versions, that are not positive integers, are still invalid:
+        assert!(playlist("0").parse::<MediaPlaylist>().is_err());
+        assert!(playlist("garbage").parse::<MediaPlaylist>().is_err());
+    }
+
+    #[test]
+    fn test_discontinuity_count() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/ad-1.ts\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/ad-2.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.discontinuity_count(), 2);
+        assert_eq!(
+            playlist.discontinuity_indices().collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.discontinuity_count(), 0);
+        assert_eq!(playlist.discontinuity_indices().next(), None);
+    }
+
+    #[test]
+    fn test_segment_discontinuity_sequences() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/third.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.discontinuity_sequence_tag(), None);
+        assert_eq!(playlist.segment_discontinuity_sequences(), vec![0, 1, 1]);
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-DISCONTINUITY-SEQUENCE:7\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/second.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.discontinuity_sequence_tag(),
+            Some(ExtXDiscontinuitySequence::new(7))
+        );
+        assert_eq!(playlist.segment_discontinuity_sequences(), vec![7, 8]);
+
+        // the tag has to appear before the first segment:
+        assert!("#EXTM3U\n\
+                 #EXT-X-TARGETDURATION:10\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/first.ts\n\
+                 #EXT-X-DISCONTINUITY-SEQUENCE:7\n\
+                 #EXTINF:10,\n\
+                 http://media.example.com/second.ts"
+            .parse::<MediaPlaylist>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_enumerate_segments() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-MEDIA-SEQUENCE:100\n\
+                        #EXT-X-DISCONTINUITY-SEQUENCE:2\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:9,\n\
+                        http://media.example.com/second.ts\n\
+                        #EXT-X-DISCONTINUITY\n\
+                        #EXTINF:8,\n\
+                        http://media.example.com/third.ts"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist
+                .enumerate_segments()
+                .map(|s| (
+                    s.index(),
+                    s.media_sequence(),
+                    s.discontinuity_sequence(),
+                    s.start(),
+                    s.segment().uri().as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    0,
+                    100,
+                    2,
+                    Duration::from_secs(0),
+                    "http://media.example.com/first.ts"
+                ),
+                (
+                    1,
+                    101,
+                    2,
+                    Duration::from_secs(10),
+                    "http://media.example.com/second.ts"
+                ),
+                (
+                    2,
+                    102,
+                    3,
+                    Duration::from_secs(19),
+                    "http://media.example.com/third.ts"
+                ),
+            ]
+        );
+
+        for info in playlist.enumerate_segments() {
+            assert_eq!(info.segment(), &playlist.segments()[info.index()]);
+        }
+    }
+
+    #[test]
+    fn test_parse_with_query_params() {
+        let playlist = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DEFINE:QUERYPARAM=\"token\"\n",
+            "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"media.example.com\"\n",
+            "#EXTINF:10,\n",
+            "http://{$host}/first.ts?token={$token}\n",
+            "#EXTINF:10,\n",
+            "http://{$host}/second.ts?token={$token}\n",
+        );
+
+        let mut query_params = HashMap::new();
+        query_params.insert("token".to_string(), "abc123".to_string());
+
+        let playlist = MediaPlaylist::parse_with_query_params(playlist, &query_params).unwrap();
+
+        assert_eq!(
+            playlist
+                .segments()
+                .iter()
+                .map(|s| s.uri().as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "http://media.example.com/first.ts?token=abc123",
+                "http://media.example.com/second.ts?token=abc123",
+            ]
+        );
+        assert_eq!(playlist.unknown_tags().len(), 2);
+
+        // the query parameter is not provided
+        assert!(MediaPlaylist::parse_with_query_params(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-DEFINE:QUERYPARAM=\"token\"\n\
+             #EXTINF:10,\n\
+             http://media.example.com/first.ts?token={$token}\n",
+            &HashMap::new()
+        )
+        .is_err());
+
+        // comments are not modified and the values of the variables are not
+        // searched for references:
+        let mut params = HashMap::new();
+        params.insert("a".to_string(), "{$b}".to_string());
+
+        let playlist = MediaPlaylist::parse_with_query_params(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXT-X-DEFINE:QUERYPARAM=\"a\"\n\
+             #EXT-X-DEFINE:NAME=\"b\",VALUE=\"value\"\n\
+             # a comment with an {$undefined} variable\n\
+             #EXTINF:10,\n\
+             http://media.example.com/{$a}/{$b}.ts\n",
+            &params,
+        )
+        .unwrap();
+
+        assert_eq!(
+            playlist.segments()[0].uri(),
+            "http://media.example.com/{$b}/value.ts"
+        );
+
+        // the variable is referenced before it is defined
+        assert!(MediaPlaylist::parse_with_query_params(
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:10,\n\
+             http://media.example.com/first.ts?token={$token}\n\
+             #EXT-X-DEFINE:QUERYPARAM=\"token\"\n",
+            &query_params
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_on_progress() {
+        let mut playlist = String::from("#EXTM3U\n#EXT-X-TARGETDURATION:10\n");
+
+        for i in 0..2500 {
+            playlist += &format!("#EXTINF:10,\nhttp://media.example.com/{}.ts\n", i);
+        }
+
+        let mut progress = vec![];
+
+        let result = MediaPlaylist::builder()
+            .on_progress(|count| progress.push(count))
+            .parse(&playlist)
+            .unwrap();
+
+        assert_eq!(result.segments().len(), 2500);
+        assert_eq!(progress, vec![1000, 2000]);
+
+        let mut progress = vec![];
+
+        MediaPlaylist::builder()
+            .on_progress(|count| progress.push(count))
+            .interval(300)
+            .parse(&playlist)
+            .unwrap();
+
+        assert_eq!(progress.len(), 8);
+        assert!(progress.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_auto_raise_target_duration() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:8\n\
+                        #EXTINF:6,\n\
+                        http://media.example.com/first.ts\n\
+                        #EXTINF:9.6,\n\
+                        http://media.example.com/second.ts\n";
+
+        // the declared target duration is exceeded:
+        assert!(playlist.parse::<MediaPlaylist>().is_err());
+
+        let playlist = MediaPlaylist::builder()
+            .auto_raise_target_duration(true)
+            .parse(playlist)
+            .unwrap();
+
+        assert_eq!(
+            playlist.target_duration_tag(),
+            ExtXTargetDuration::new(Duration::from_secs(10))
+        );
+        assert_eq!(
+            playlist.to_string(),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:3\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:6,\n\
+             http://media.example.com/first.ts\n\
+             #EXTINF:9.6,\n\
+             http://media.example.com/second.ts\n"
+                .to_string()
+        );
+
+        // a target duration, that is large enough, is kept:
+        let playlist = MediaPlaylist::builder()
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(12)))
+            .auto_raise_target_duration(true)
+            .segments(vec![MediaSegment::builder()
+                .inf_tag(ExtInf::new(Duration::from_secs_f64(9.6)))
+                .uri("http://media.example.com/first.ts")
+                .build()
+                .unwrap()])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            playlist.target_duration_tag(),
+            ExtXTargetDuration::new(Duration::from_secs(12))
+        );
+
+        // the option of the builder is not part of the playlist:
+        assert_eq!(
+            playlist,
+            MediaPlaylist::builder()
+                .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(12)))
+                .segments(playlist.segments().clone())
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_snap_durations() {
+        let mut playlist = "#EXTM3U\n\
+                            #EXT-X-TARGETDURATION:10\n\
+                            #EXTINF:9.009,\n\
+                            http://media.example.com/first.ts\n\
+                            #EXTINF:9.509,\n\
+                            http://media.example.com/second.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        playlist.snap_durations(Duration::from_secs(1)).unwrap();
+
+        assert_eq!(
+            playlist
+                .segments()
+                .iter()
+                .map(|s| s.inf_tag().duration())
+                .collect::<Vec<_>>(),
+            vec![Duration::from_secs(9), Duration::from_secs(10)]
+        );
+
+        assert!(playlist.snap_durations(Duration::from_secs(0)).is_err());
+
+        // snapping to 4 seconds would exceed the target duration:
+        assert!(playlist.snap_durations(Duration::from_secs(4)).is_err());
+        assert_eq!(
+            playlist.segments()[1].inf_tag().duration(),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn test_map_keys() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-VERSION:6\n\
+                        #EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key.bin\"\n\
+                        #EXT-X-MAP:URI=\"init.mp4\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.m4s\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let map = playlist.segments()[0].map_tag().as_ref().unwrap();
+        let key = ExtXKey::new(EncryptionMethod::Aes128, "https://example.com/key.bin");
+
+        assert!(map.is_encrypted());
+        assert_eq!(map.keys(), &vec![key.clone()]);
+        assert_eq!(map.decryption_info(), Some(&key));
+        assert_eq!(
+            map.decryption_info(),
+            playlist.segments()[0].decryption_info()
+        );
+
+        // a map without a preceding key is not encrypted:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-MAP:URI=\"init.mp4\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.m4s\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.segments()[0]
+                .map_tag()
+                .as_ref()
+                .unwrap()
+                .decryption_info(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_key_removal() {
+        let playlist = MediaPlaylist::builder()
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .segments(vec![
+                MediaSegment::builder()
+                    .keys(vec![ExtXKey::new(
+                        EncryptionMethod::Aes128,
+                        "https://example.com/key.bin",
+                    )])
+                    .inf_tag(ExtInf::new(Duration::from_secs(10)))
+                    .uri("http://media.example.com/first.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .inf_tag(ExtInf::new(Duration::from_secs(10)))
+                    .uri("http://media.example.com/second.ts")
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        assert!(playlist.to_string().contains("#EXT-X-KEY:METHOD=NONE\n"));
+
+        let parsed = playlist.to_string().parse::<MediaPlaylist>().unwrap();
+
+        assert_eq!(parsed.segments()[1].keys().len(), 0);
+        assert_eq!(parsed, playlist);
+    }
+
+    #[test]
+    fn test_emit_for_version() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXTINF:9.009,\n\
+                        http://media.example.com/first.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.required_version(), ProtocolVersion::V3);
+        assert_eq!(
+            playlist.emit_for_version(ProtocolVersion::V3).unwrap(),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:3\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:9.009,\n\
+             http://media.example.com/first.ts\n"
+                .to_string()
+        );
+        assert_eq!(
+            playlist.emit_for_version(ProtocolVersion::V7).unwrap(),
+            "#EXTM3U\n\
+             #EXT-X-VERSION:7\n\
+             #EXT-X-TARGETDURATION:10\n\
+             #EXTINF:9.009,\n\
+             http://media.example.com/first.ts\n"
+                .to_string()
+        );
+
+        // the EXT-X-MAP tag requires a higher version:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:10\n\
+                        #EXT-X-MAP:URI=\"init.mp4\"\n\
+                        #EXTINF:10,\n\
+                        http://media.example.com/first.m4s\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(playlist.required_version(), ProtocolVersion::V6);
+        assert_eq!(
+            playlist
+                .emit_for_version(ProtocolVersion::V3)
+                .unwrap_err()
+                .to_string(),
+            "CustomError: The playlist requires version 6, but version 3 was requested".to_string()
+        );
+        assert!(playlist.emit_for_version(ProtocolVersion::V6).is_ok());
+    }
+
+    #[test]
+    fn test_estimated_presentation_delay() {
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/first.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.estimated_presentation_delay(),
+            Duration::from_secs(12)
+        );
+
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES,HOLD-BACK=8.5\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/first.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.estimated_presentation_delay(),
+            Duration::from_secs_f64(8.5)
+        );
+
+        // a server control tag without a hold back:
+        let playlist = "#EXTM3U\n\
+                        #EXT-X-TARGETDURATION:4\n\
+                        #EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES\n\
+                        #EXTINF:4,\n\
+                        http://media.example.com/first.ts\n"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        assert_eq!(
+            playlist.estimated_presentation_delay(),
+            Duration::from_secs(12)
+        );
+    }
+
+    #[test]
+    fn test_enforce_pdt_monotonicity() {
+        let segment = |uri: &str, date_time: &str, discontinuity: bool| {
+            let mut segment = MediaSegment::builder();
+            segment
+                .inf_tag(ExtInf::new(Duration::from_secs(10)))
+                .program_date_time_tag(date_time.parse::<ExtXProgramDateTime>().unwrap())
+                .uri(uri);
+
+            if discontinuity {
+                segment.discontinuity_tag(ExtXDiscontinuity);
+            }
+
+            segment.build().unwrap()
+        };
+
+        let mut builder = MediaPlaylist::builder();
+        builder
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .push_segment(segment(
+                "first.ts",
+                "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:20.000+00:00",
+                false,
+            ))
+            .push_segment(segment(
+                "second.ts",
+                "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:10.000+00:00",
+                false,
+            ));
+
+        // the check is disabled by default:
+        assert!(builder.build().is_ok());
+
+        builder.enforce_pdt_monotonicity(true);
+
+        assert_eq!(
+            builder.build().unwrap_err(),
+            "CustomError: Program date time goes backwards: \
+             2010-02-19T14:54:20+00:00 -> 2010-02-19T14:54:10+00:00, \
+             uri=\"second.ts\""
+                .to_string()
+        );
+
+        // a discontinuity starts a new range:
+        let mut builder = MediaPlaylist::builder();
+        builder
+            .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+            .enforce_pdt_monotonicity(true)
+            .push_segment(segment(
+                "first.ts",
+                "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:20.000+00:00",
+                false,
+            ))
+            .push_segment(segment(
+                "second.ts",
+                "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:10.000+00:00",
+                true,
+            ));
+
+        let playlist = builder.build().unwrap();
+
+        // the option of the builder is not part of the playlist:
+        assert_eq!(
+            playlist,
+            MediaPlaylist::builder()
+                .target_duration_tag(ExtXTargetDuration::new(Duration::from_secs(10)))
+                .segments(playlist.segments().clone())
+                .build()
+                .unwrap()
+        );
     }
 }
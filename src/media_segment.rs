@@ -1,9 +1,11 @@
 use std::fmt;
+use std::time::Duration;
 
 use derive_builder::Builder;
 
 use crate::tags::{
-    ExtInf, ExtXByteRange, ExtXDateRange, ExtXDiscontinuity, ExtXKey, ExtXMap, ExtXProgramDateTime,
+    ExtInf, ExtXBitrate, ExtXByteRange, ExtXDateRange, ExtXDiscontinuity, ExtXKey, ExtXMap,
+    ExtXProgramDateTime,
 };
 use crate::types::ProtocolVersion;
 use crate::{Encrypted, RequiredVersion};
@@ -22,6 +24,9 @@ pub struct MediaSegment {
     /// Sets an [`ExtXByteRange`] tag.
     byte_range_tag: Option<ExtXByteRange>,
     #[builder(default)]
+    /// Sets an [`ExtXBitrate`] tag.
+    bitrate_tag: Option<ExtXBitrate>,
+    #[builder(default)]
     /// Sets an [`ExtXDateRange`] tag.
     date_range_tag: Option<ExtXDateRange>,
     #[builder(default)]
@@ -66,6 +71,73 @@ impl MediaSegment {
         self
     }
 
+    /// Returns the duration of the media segment in whole milliseconds.
+    ///
+    /// This is a shorthand for [`ExtInf::duration_millis`].
+    pub const fn duration_millis(&self) -> u128 { self.inf_tag.duration_millis() }
+
+    /// Returns the number of frames, that fit into the duration of the media
+    /// segment at the given frame rate.
+    ///
+    /// The result is rounded to the nearest frame; a duration, that ends
+    /// exactly halfway between two frames, is rounded up. A negative or `NaN`
+    /// frame rate results in `0`.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaSegment;
+    /// use hls_m3u8::tags::ExtInf;
+    /// use std::time::Duration;
+    ///
+    /// let segment = MediaSegment::builder()
+    ///     .inf_tag(ExtInf::new(Duration::from_secs(2)))
+    ///     .uri("http://www.example.com/segment.ts")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(segment.frame_count(25.0), 50);
+    /// ```
+    pub fn frame_count(&self, fps: f64) -> u64 {
+        (self.inf_tag.duration().as_secs_f64() * fps).round() as u64
+    }
+
+    /// Returns a key, that identifies the media segment independent of the
+    /// precision of its duration.
+    ///
+    /// The duration is rounded to whole milliseconds, so two segments, whose
+    /// durations only differ by less than half a millisecond, have the same
+    /// key. This can be used to deduplicate segments across re-encodes.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::MediaSegment;
+    /// use hls_m3u8::tags::ExtInf;
+    /// use std::time::Duration;
+    ///
+    /// let first = MediaSegment::builder()
+    ///     .inf_tag(ExtInf::new(Duration::from_secs_f64(9.009)))
+    ///     .uri("http://www.example.com/segment.ts")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let second = MediaSegment::builder()
+    ///     .inf_tag(ExtInf::new(Duration::from_secs_f64(9.0090001)))
+    ///     .uri("http://www.example.com/segment.ts")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_ne!(first, second);
+    /// assert_eq!(first.semantic_key(), second.semantic_key());
+    /// ```
+    pub fn semantic_key(&self) -> SemanticKey {
+        let mut segment = self.clone();
+        segment
+            .inf_tag
+            .set_duration(Duration::from_millis(self.duration_millis() as u64));
+
+        SemanticKey(segment.to_string())
+    }
+
     /// Returns the [`ExtXByteRange`] tag associated with the media segment.
     pub const fn byte_range_tag(&self) -> Option<ExtXByteRange> { self.byte_range_tag }
 
@@ -78,6 +150,31 @@ impl MediaSegment {
         self
     }
 
+    /// Returns the [`ExtXBitrate`] tag associated with the media segment.
+    ///
+    /// # Note
+    /// An [`ExtXBitrate`] tag applies to every following media segment until
+    /// the next [`ExtXBitrate`] tag, so this returns the inherited tag and not
+    /// only a tag, that directly precedes the segment in the playlist.
+    pub const fn bitrate_tag(&self) -> Option<ExtXBitrate> { self.bitrate_tag }
+
+    /// Returns the approximate bit rate of the media segment in kilobits per
+    /// second.
+    ///
+    /// This is a shorthand for the value of [`bitrate_tag`].
+    ///
+    /// [`bitrate_tag`]: #method.bitrate_tag
+    pub fn bitrate(&self) -> Option<u64> { self.bitrate_tag.map(ExtXBitrate::bitrate) }
+
+    /// Sets the [`ExtXBitrate`] tag associated with the media segment.
+    pub fn set_bitrate_tag<T>(&mut self, value: Option<T>) -> &mut Self
+    where
+        T: Into<ExtXBitrate>,
+    {
+        self.bitrate_tag = value.map(Into::into);
+        self
+    }
+
     /// Returns the [`ExtXDateRange`] tag associated with the media segment.
     pub const fn date_range_tag(&self) -> &Option<ExtXDateRange> { &self.date_range_tag }
 
@@ -143,10 +240,31 @@ impl MediaSegmentBuilder {
     }
 }
 
-impl fmt::Display for MediaSegment {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for value in &self.keys {
-            writeln!(f, "{}", value)?;
+impl MediaSegment {
+    /// Writes the segment, skipping the [`ExtXKey`] and [`ExtXBitrate`] tags,
+    /// that are inherited from the `previous` segment.
+    pub(crate) fn write_with<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        previous: Option<&Self>,
+    ) -> fmt::Result {
+        if previous.map(|p| &p.keys) != Some(&self.keys) {
+            // A key can only be replaced by a key with the same `KEYFORMAT`, so a
+            // key without a replacement has to be removed with `METHOD=NONE`.
+            let has_key_format =
+                |key: &ExtXKey| self.keys.iter().any(|k| k.key_format() == key.key_format());
+            let removes_key = previous
+                .into_iter()
+                .flat_map(|p| &p.keys)
+                .any(|key| !has_key_format(key));
+
+            if removes_key {
+                writeln!(f, "{}", ExtXKey::empty())?;
+            }
+
+            for value in &self.keys {
+                writeln!(f, "{}", value)?;
+            }
         }
         if let Some(value) = &self.map_tag {
             writeln!(f, "{}", value)?;
@@ -154,6 +272,11 @@ impl fmt::Display for MediaSegment {
         if let Some(value) = &self.byte_range_tag {
             writeln!(f, "{}", value)?;
         }
+        if let Some(value) = &self.bitrate_tag {
+            if previous.map(|p| p.bitrate_tag) != Some(self.bitrate_tag) {
+                writeln!(f, "{}", value)?;
+            }
+        }
         if let Some(value) = &self.date_range_tag {
             writeln!(f, "{}", value)?;
         }
@@ -169,12 +292,24 @@ impl fmt::Display for MediaSegment {
     }
 }
 
+/// A key, that identifies a [`MediaSegment`] independent of the precision of
+/// its duration.
+///
+/// See [`MediaSegment::semantic_key`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SemanticKey(String);
+
+impl fmt::Display for MediaSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { self.write_with(f, None) }
+}
+
 impl RequiredVersion for MediaSegment {
     fn required_version(&self) -> ProtocolVersion {
         required_version![
             self.keys,
             self.map_tag,
             self.byte_range_tag,
+            self.bitrate_tag,
             self.date_range_tag,
             self.discontinuity_tag,
             self.program_date_time_tag,
@@ -195,6 +330,39 @@ mod tests {
     use pretty_assertions::assert_eq;
     use std::time::Duration;
 
+    #[test]
+    fn test_duration_millis() {
+        assert_eq!(
+            MediaSegment::builder()
+                .inf_tag("#EXTINF:9.009,".parse::<ExtInf>().unwrap())
+                .uri("http://www.uri.com/")
+                .build()
+                .unwrap()
+                .duration_millis(),
+            9009
+        );
+    }
+
+    #[test]
+    fn test_frame_count() {
+        let segment = |duration: &str| {
+            MediaSegment::builder()
+                .inf_tag(format!("#EXTINF:{},", duration).parse::<ExtInf>().unwrap())
+                .uri("http://www.uri.com/")
+                .build()
+                .unwrap()
+        };
+
+        // 10.01 * 23.976 = 239.99976
+        assert_eq!(segment("10.01").frame_count(23.976), 240);
+        // 4.004 * 23.976 = 95.999904
+        assert_eq!(segment("4.004").frame_count(23.976), 96);
+        assert_eq!(segment("2").frame_count(25.0), 50);
+        // 2.5 * 25 = 62.5
+        assert_eq!(segment("2.5").frame_count(25.0), 63);
+        assert_eq!(segment("2.5").frame_count(-25.0), 0);
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -218,4 +386,45 @@ mod tests {
                 .to_string()
         );
     }
+
+    #[test]
+    fn test_semantic_key() {
+        use std::collections::HashSet;
+
+        let segment = |inf_tag: &str| {
+            MediaSegment::builder()
+                .inf_tag(inf_tag.parse::<ExtInf>().unwrap())
+                .uri("http://www.uri.com/")
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(
+            segment("#EXTINF:9.009,").semantic_key(),
+            segment("#EXTINF:9.0090,").semantic_key()
+        );
+        assert_eq!(
+            segment("#EXTINF:9.009,").semantic_key(),
+            segment("#EXTINF:9.00900004,").semantic_key()
+        );
+        assert_ne!(
+            segment("#EXTINF:9.009,").semantic_key(),
+            segment("#EXTINF:9.010,").semantic_key()
+        );
+        assert_ne!(
+            segment("#EXTINF:9.009,").semantic_key(),
+            segment("#EXTINF:9.009,title").semantic_key()
+        );
+
+        let keys = [
+            segment("#EXTINF:9.009,"),
+            segment("#EXTINF:9.0090,"),
+            segment("#EXTINF:9.00900004,"),
+        ]
+        .iter()
+        .map(MediaSegment::semantic_key)
+        .collect::<HashSet<_>>();
+
+        assert_eq!(keys.len(), 1);
+    }
 }
@@ -0,0 +1,153 @@
+use crate::tags;
+use crate::Error;
+
+/// Tags, that are only allowed in a [`MasterPlaylist`].
+///
+/// [`MasterPlaylist`]: crate::MasterPlaylist
+const MASTER_PLAYLIST_TAGS: &[&str] = &[
+    tags::ExtXMedia::PREFIX,
+    tags::ExtXStreamInf::PREFIX,
+    tags::ExtXIFrameStreamInf::PREFIX,
+    tags::ExtXSessionData::PREFIX,
+    tags::ExtXSessionKey::PREFIX,
+];
+
+/// Tags, that are only allowed in a [`MediaPlaylist`].
+///
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+const MEDIA_PLAYLIST_TAGS: &[&str] = &[
+    tags::ExtInf::PREFIX,
+    tags::ExtXByteRange::PREFIX,
+    tags::ExtXBitrate::PREFIX,
+    tags::ExtXDiscontinuity::PREFIX,
+    tags::ExtXKey::PREFIX,
+    tags::ExtXMap::PREFIX,
+    tags::ExtXProgramDateTime::PREFIX,
+    tags::ExtXDateRange::PREFIX,
+    tags::ExtXTargetDuration::PREFIX,
+    tags::ExtXMediaSequence::PREFIX,
+    tags::ExtXEndList::PREFIX,
+    tags::ExtXPlaylistType::PREFIX,
+    tags::ExtXIFramesOnly::PREFIX,
+];
+
+/// The kind of a playlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaylistKind {
+    /// The playlist is a [`MasterPlaylist`].
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    Master,
+    /// The playlist is a [`MediaPlaylist`].
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    Media,
+}
+
+/// Detects, whether the given m3u8 file is a [`MasterPlaylist`] or a
+/// [`MediaPlaylist`], by looking for tags, that are only allowed in one of
+/// them.
+///
+/// # Example
+/// ```
+/// use hls_m3u8::{playlist_kind, PlaylistKind};
+///
+/// let input = "#EXTM3U\n\
+///              #EXT-X-TARGETDURATION:10\n\
+///              #EXTINF:9.009,\n\
+///              http://media.example.com/first.ts";
+///
+/// assert_eq!(playlist_kind(input)?, PlaylistKind::Media);
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+///
+/// # Errors
+/// This function fails, if the input contains tags of both playlist kinds
+/// or none of them.
+///
+/// [`MasterPlaylist`]: crate::MasterPlaylist
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+pub fn playlist_kind(input: &str) -> crate::Result<PlaylistKind> {
+    let mut master_tag = None;
+    let mut media_tag = None;
+
+    for line in input.lines().map(str::trim) {
+        if master_tag.is_none() {
+            master_tag = MASTER_PLAYLIST_TAGS
+                .iter()
+                .find(|t| line.starts_with(*t))
+                .map(|t| t.trim_end_matches(':'));
+        }
+
+        if media_tag.is_none() {
+            media_tag = MEDIA_PLAYLIST_TAGS
+                .iter()
+                .find(|t| line.starts_with(*t))
+                .map(|t| t.trim_end_matches(':'));
+        }
+    }
+
+    match (master_tag, media_tag) {
+        (Some(master_tag), Some(media_tag)) => Err(Error::custom(format!(
+            "The playlist contains the master playlist tag {} and the media playlist tag {}",
+            master_tag, media_tag
+        ))),
+        (Some(_), None) => Ok(PlaylistKind::Master),
+        (None, Some(_)) => Ok(PlaylistKind::Media),
+        (None, None) => Err(Error::custom(
+            "The playlist contains neither master playlist nor media playlist tags",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_playlist_kind() {
+        assert_eq!(
+            playlist_kind(
+                "#EXTM3U\n\
+                 #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\"\n\
+                 #EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n\
+                 http://example.com/low/index.m3u8"
+            )
+            .unwrap(),
+            PlaylistKind::Master
+        );
+
+        assert_eq!(
+            playlist_kind(
+                "#EXTM3U\n\
+                 #EXT-X-TARGETDURATION:10\n\
+                 #EXT-X-MEDIA-SEQUENCE:3\n\
+                 #EXTINF:9.009,\n\
+                 http://media.example.com/first.ts"
+            )
+            .unwrap(),
+            PlaylistKind::Media
+        );
+
+        assert!(playlist_kind("#EXTM3U\n#EXT-X-VERSION:3").is_err());
+    }
+
+    #[test]
+    fn test_mixed_playlist() {
+        assert_eq!(
+            playlist_kind(
+                "#EXTM3U\n\
+                 #EXT-X-STREAM-INF:BANDWIDTH=1280000\n\
+                 http://example.com/low/index.m3u8\n\
+                 #EXTINF:9.009,\n\
+                 http://media.example.com/first.ts"
+            )
+            .unwrap_err()
+            .to_string(),
+            "CustomError: The playlist contains the master playlist tag \
+             #EXT-X-STREAM-INF and the media playlist tag #EXTINF"
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::attribute::AttributePairs;
+use crate::types::ProtocolVersion;
+use crate::utils::{quote, tag, unquote};
+use crate::{Error, RequiredVersion};
+
+/// # [4.4.6.6. EXT-X-CONTENT-STEERING]
+///
+/// The [`ExtXContentSteering`] tag allows a server to provide a Content
+/// Steering Manifest, that tells the client, which content steering pathway
+/// (see [`ExtXStreamInf::pathway_id`]) it should use.
+///
+/// This follows `draft-pantos-hls-rfc8216bis-12`, which defines the
+/// `SERVER-URI` and the `PATHWAY-ID` attributes. The draft does not define an
+/// attribute to defer the loading of the Content Steering Manifest, so a
+/// client is expected to load it, as soon as it starts to play the
+/// [`Master Playlist`].
+///
+/// The tag does not require a specific [`ProtocolVersion`].
+///
+/// [`ExtXStreamInf::pathway_id`]: crate::types::StreamInf::pathway_id
+/// [`Master Playlist`]: crate::MasterPlaylist
+/// [4.4.6.6. EXT-X-CONTENT-STEERING]:
+/// https://tools.ietf.org/html/draft-pantos-hls-rfc8216bis-12#section-4.4.6.6
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtXContentSteering {
+    server_uri: String,
+    pathway_id: Option<String>,
+}
+
+impl ExtXContentSteering {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-CONTENT-STEERING:";
+
+    /// Makes a new [`ExtXContentSteering`] tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+    /// ```
+    pub fn new<T: ToString>(server_uri: T) -> Self {
+        Self {
+            server_uri: server_uri.to_string(),
+            pathway_id: None,
+        }
+    }
+
+    /// Makes a new [`ExtXContentSteering`] tag with the given pathway.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let content_steering =
+    ///     ExtXContentSteering::with_pathway_id("https://example.com/manifest.json", "CDN-A");
+    /// ```
+    pub fn with_pathway_id<T: ToString, U: ToString>(server_uri: T, pathway_id: U) -> Self {
+        Self {
+            server_uri: server_uri.to_string(),
+            pathway_id: Some(pathway_id.to_string()),
+        }
+    }
+
+    /// Returns the `URI` of the Content Steering Manifest.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+    ///
+    /// assert_eq!(
+    ///     content_steering.server_uri(),
+    ///     &"https://example.com/manifest.json".to_string()
+    /// );
+    /// ```
+    pub const fn server_uri(&self) -> &String { &self.server_uri }
+
+    /// Sets the `URI` of the Content Steering Manifest.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let mut content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+    ///
+    /// content_steering.set_server_uri("https://example.com/steering.json");
+    /// assert_eq!(
+    ///     content_steering.server_uri(),
+    ///     &"https://example.com/steering.json".to_string()
+    /// );
+    /// ```
+    pub fn set_server_uri<T: ToString>(&mut self, value: T) -> &mut Self {
+        self.server_uri = value.to_string();
+        self
+    }
+
+    /// Returns the identifier of the pathway, that the client should use,
+    /// until it has loaded the Content Steering Manifest.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let content_steering =
+    ///     ExtXContentSteering::with_pathway_id("https://example.com/manifest.json", "CDN-A");
+    ///
+    /// assert_eq!(content_steering.pathway_id(), &Some("CDN-A".to_string()));
+    /// ```
+    pub const fn pathway_id(&self) -> &Option<String> { &self.pathway_id }
+
+    /// Sets the identifier of the pathway, that the client should use, until
+    /// it has loaded the Content Steering Manifest.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let mut content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+    ///
+    /// assert_eq!(content_steering.pathway_id(), &None);
+    ///
+    /// content_steering.set_pathway_id(Some("CDN-A"));
+    /// assert_eq!(content_steering.pathway_id(), &Some("CDN-A".to_string()));
+    /// ```
+    pub fn set_pathway_id<T: ToString>(&mut self, value: Option<T>) -> &mut Self {
+        self.pathway_id = value.map(|v| v.to_string());
+        self
+    }
+}
+
+impl RequiredVersion for ExtXContentSteering {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXContentSteering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::PREFIX)?;
+        write!(f, "SERVER-URI={}", quote(&self.server_uri))?;
+
+        if let Some(value) = &self.pathway_id {
+            write!(f, ",PATHWAY-ID={}", quote(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for ExtXContentSteering {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut server_uri = None;
+        let mut pathway_id = None;
+
+        for (key, value) in input.parse::<AttributePairs>()? {
+            match key.as_str() {
+                "SERVER-URI" => server_uri = Some(unquote(value)),
+                "PATHWAY-ID" => pathway_id = Some(unquote(value)),
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        let server_uri = server_uri.ok_or_else(|| Error::missing_value("SERVER-URI"))?;
+
+        Ok(Self {
+            server_uri,
+            pathway_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXContentSteering::new("https://example.com/manifest.json").to_string(),
+            "#EXT-X-CONTENT-STEERING:SERVER-URI=\"https://example.com/manifest.json\"".to_string()
+        );
+
+        assert_eq!(
+            ExtXContentSteering::with_pathway_id("https://example.com/manifest.json", "CDN-A")
+                .to_string(),
+            "#EXT-X-CONTENT-STEERING:\
+             SERVER-URI=\"https://example.com/manifest.json\",\
+             PATHWAY-ID=\"CDN-A\""
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXContentSteering::new("https://example.com/manifest.json"),
+            "#EXT-X-CONTENT-STEERING:SERVER-URI=\"https://example.com/manifest.json\""
+                .parse()
+                .unwrap()
+        );
+
+        assert_eq!(
+            ExtXContentSteering::with_pathway_id("https://example.com/manifest.json", "CDN-A"),
+            "#EXT-X-CONTENT-STEERING:\
+             PATHWAY-ID=\"CDN-A\",\
+             SERVER-URI=\"https://example.com/manifest.json\",\
+             UNKNOWN=IGNORED"
+                .parse()
+                .unwrap()
+        );
+
+        assert!("#EXT-X-CONTENT-STEERING:PATHWAY-ID=\"CDN-A\""
+            .parse::<ExtXContentSteering>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tag =
+            ExtXContentSteering::with_pathway_id("https://example.com/manifest.json", "CDN-A");
+
+        assert_eq!(tag.to_string().parse::<ExtXContentSteering>().unwrap(), tag);
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXContentSteering::new("https://example.com/manifest.json").required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
@@ -78,8 +78,19 @@ impl ExtXIFrameStreamInfBuilder {
         self
     }
 
+    /// The content steering pathway, that the stream belongs to.
+    pub fn pathway_id<T: Into<String>>(&mut self, value: T) -> &mut Self {
+        self.stream_inf.pathway_id(value);
+        self
+    }
+
     /// Build an [`ExtXIFrameStreamInf`].
     pub fn build(&self) -> crate::Result<ExtXIFrameStreamInf> {
+        // `BANDWIDTH` is the only attribute without a default value:
+        if !self.stream_inf.has_bandwidth() {
+            return Err(Error::missing_attribute("BANDWIDTH"));
+        }
+
         Ok(ExtXIFrameStreamInf {
             uri: self
                 .uri
@@ -6,7 +6,7 @@ use derive_builder::Builder;
 use crate::attribute::AttributePairs;
 use crate::types::{Channels, InStreamId, MediaType, ProtocolVersion};
 use crate::utils::{parse_yes_or_no, quote, tag, unquote};
-use crate::{Error, RequiredVersion};
+use crate::{AttributeOrder, Error, RequiredVersion};
 
 /// # [4.4.5.1. EXT-X-MEDIA]
 ///
@@ -140,6 +140,72 @@ impl ExtXMediaBuilder {
 
         Ok(())
     }
+
+    /// Builds a new [`ExtXMedia`] like [`ExtXMediaBuilder::build`], but also
+    /// validates the [`language`] and [`assoc_language`] attributes.
+    ///
+    /// Only the structure of the subtags is checked, they are not looked up
+    /// in the language subtag registry.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMedia;
+    /// use hls_m3u8::types::MediaType;
+    ///
+    /// let media = ExtXMedia::builder()
+    ///     .media_type(MediaType::Audio)
+    ///     .group_id("audio")
+    ///     .name("English")
+    ///     .language("en-US")
+    ///     .build_with_validated_language_tags()?;
+    ///
+    /// assert_eq!(media.language(), &Some("en-US".to_string()));
+    /// # Ok::<(), String>(())
+    /// ```
+    ///
+    /// # Errors
+    /// This function fails, if one of those attributes is not a well-formed
+    /// [`RFC5646`] language tag or if [`ExtXMediaBuilder::build`] fails.
+    ///
+    /// [`language`]: #method.language
+    /// [`assoc_language`]: #method.assoc_language
+    /// [`RFC5646`]: https://tools.ietf.org/html/rfc5646
+    pub fn build_with_validated_language_tags(&self) -> Result<ExtXMedia, String> {
+        for value in [&self.language, &self.assoc_language].iter() {
+            if let Some(Some(value)) = value {
+                if !is_language_tag(value) {
+                    return Err(
+                        Error::custom(format!("Invalid language tag: {:?}", value)).to_string()
+                    );
+                }
+            }
+        }
+
+        self.build()
+    }
+}
+
+/// Checks, that `value` has the structure of an [`RFC5646`] language tag.
+///
+/// Every subtag has to consist of 1 to 8 alphanumeric characters and the
+/// primary language subtag has to consist of 2 to 8 letters, unless it is a
+/// private use (`x`) or grandfathered (`i`) prefix.
+///
+/// [`RFC5646`]: https://tools.ietf.org/html/rfc5646#section-2.1
+fn is_language_tag(value: &str) -> bool {
+    let mut subtags = value.split('-');
+
+    let is_valid_primary = match subtags.next() {
+        Some("x") | Some("X") | Some("i") | Some("I") => value.len() > 2,
+        Some(primary) => {
+            (2..=8).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic())
+        }
+        None => false,
+    };
+
+    is_valid_primary
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
 }
 
 impl ExtXMedia {
@@ -628,6 +694,72 @@ impl ExtXMedia {
         self.channels = value.map(Into::into);
         self
     }
+
+    /// Returns a key, that can be used to sort renditions for displaying them
+    /// to a user.
+    ///
+    /// Renditions are ordered by their [`MediaType`], the `default`
+    /// rendition of each type comes first and the remaining renditions are
+    /// ordered by their name.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMedia;
+    /// use hls_m3u8::types::MediaType;
+    ///
+    /// let mut english = ExtXMedia::new(MediaType::Audio, "audio", "English");
+    /// english.set_default(true);
+    ///
+    /// let french = ExtXMedia::new(MediaType::Audio, "audio", "French");
+    /// let subtitles = ExtXMedia::new(MediaType::Subtitles, "subs", "Deutsch");
+    ///
+    /// assert!(english.display_sort_key() < french.display_sort_key());
+    /// assert!(french.display_sort_key() < subtitles.display_sort_key());
+    /// ```
+    pub fn display_sort_key(&self) -> (u8, String) {
+        let media_type = match self.media_type {
+            MediaType::Audio => 0,
+            MediaType::Video => 1,
+            MediaType::Subtitles => 2,
+            MediaType::ClosedCaptions => 3,
+        };
+
+        (
+            media_type * 2 + u8::from(!self.is_default),
+            self.name.clone(),
+        )
+    }
+
+    /// Returns `true`, if the [`characteristics`] contain a UTI, that marks
+    /// the rendition as an accessibility rendition (for example subtitles for
+    /// the deaf and hard of hearing or an audio description of the video).
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMedia;
+    /// use hls_m3u8::types::MediaType;
+    ///
+    /// let mut media = ExtXMedia::new(MediaType::Audio, "audio", "Audio Description");
+    /// assert!(!media.is_accessibility());
+    ///
+    /// media.set_characteristics(Some("public.accessibility.describes-video"));
+    /// assert!(media.is_accessibility());
+    /// ```
+    ///
+    /// [`characteristics`]: #method.characteristics
+    pub fn is_accessibility(&self) -> bool {
+        const ACCESSIBILITY_UTIS: &[&str] = &[
+            "public.accessibility.transcribes-spoken-dialog",
+            "public.accessibility.describes-music-and-sound",
+            "public.accessibility.describes-video",
+            "public.easy-to-read",
+        ];
+
+        self.characteristics
+            .iter()
+            .flat_map(|characteristics| characteristics.split(','))
+            .any(|uti| ACCESSIBILITY_UTIS.contains(&uti.trim()))
+    }
 }
 
 impl RequiredVersion for ExtXMedia {
@@ -643,21 +775,52 @@ impl RequiredVersion for ExtXMedia {
     }
 }
 
-impl fmt::Display for ExtXMedia {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl ExtXMedia {
+    /// Writes the tag with its attributes in the given [`AttributeOrder`].
+    pub(crate) fn write_with<W: fmt::Write>(
+        &self,
+        f: &mut W,
+        order: AttributeOrder,
+    ) -> fmt::Result {
         write!(f, "{}", Self::PREFIX)?;
         write!(f, "TYPE={}", self.media_type)?;
-        if let Some(value) = &self.uri {
-            write!(f, ",URI={}", quote(value))?;
-        }
-        write!(f, ",GROUP-ID={}", quote(&self.group_id))?;
-        if let Some(value) = &self.language {
-            write!(f, ",LANGUAGE={}", quote(value))?;
-        }
-        if let Some(value) = &self.assoc_language {
-            write!(f, ",ASSOC-LANGUAGE={}", quote(value))?;
+
+        match order {
+            AttributeOrder::Default => {
+                if let Some(value) = &self.uri {
+                    write!(f, ",URI={}", quote(value))?;
+                }
+                write!(f, ",GROUP-ID={}", quote(&self.group_id))?;
+                if let Some(value) = &self.language {
+                    write!(f, ",LANGUAGE={}", quote(value))?;
+                }
+                if let Some(value) = &self.assoc_language {
+                    write!(f, ",ASSOC-LANGUAGE={}", quote(value))?;
+                }
+                write!(f, ",NAME={}", quote(&self.name))?;
+                self.write_flags(f)
+            }
+            AttributeOrder::AppleCompatible => {
+                write!(f, ",GROUP-ID={}", quote(&self.group_id))?;
+                write!(f, ",NAME={}", quote(&self.name))?;
+                if let Some(value) = &self.language {
+                    write!(f, ",LANGUAGE={}", quote(value))?;
+                }
+                if let Some(value) = &self.assoc_language {
+                    write!(f, ",ASSOC-LANGUAGE={}", quote(value))?;
+                }
+                self.write_flags(f)?;
+                if let Some(value) = &self.uri {
+                    write!(f, ",URI={}", quote(value))?;
+                }
+                Ok(())
+            }
         }
-        write!(f, ",NAME={}", quote(&self.name))?;
+    }
+
+    /// Writes the attributes, that follow the `NAME` attribute in both
+    /// orders.
+    fn write_flags<W: fmt::Write>(&self, f: &mut W) -> fmt::Result {
         if self.is_default {
             write!(f, ",DEFAULT=YES")?;
         }
@@ -680,6 +843,12 @@ impl fmt::Display for ExtXMedia {
     }
 }
 
+impl fmt::Display for ExtXMedia {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_with(f, AttributeOrder::Default)
+    }
+}
+
 impl FromStr for ExtXMedia {
     type Err = Error;
 
@@ -1305,6 +1474,77 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_validate_language_tags() {
+        assert!(ExtXMedia::builder()
+            .media_type(MediaType::Audio)
+            .group_id("audio")
+            .name("English")
+            .language("en-US")
+            .assoc_language("en")
+            .build_with_validated_language_tags()
+            .is_ok());
+
+        assert_eq!(
+            ExtXMedia::builder()
+                .media_type(MediaType::Audio)
+                .group_id("audio")
+                .name("English")
+                .language("english_US")
+                .build_with_validated_language_tags()
+                .unwrap_err(),
+            "CustomError: Invalid language tag: \"english_US\"".to_string()
+        );
+
+        assert!(ExtXMedia::builder()
+            .media_type(MediaType::Audio)
+            .group_id("audio")
+            .name("English")
+            .assoc_language("en-")
+            .build_with_validated_language_tags()
+            .is_err());
+
+        // the validation is opt-in:
+        assert!(ExtXMedia::builder()
+            .media_type(MediaType::Audio)
+            .group_id("audio")
+            .name("English")
+            .language("english_US")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_is_accessibility() {
+        let media = "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English (AD)\",\
+                     LANGUAGE=\"en\",CHARACTERISTICS=\"public.accessibility.describes-video\",\
+                     URI=\"en-ad/prog_index.m3u8\""
+            .parse::<ExtXMedia>()
+            .unwrap();
+
+        assert!(media.is_accessibility());
+
+        let media = "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English (SDH)\",\
+                     CHARACTERISTICS=\"public.accessibility.transcribes-spoken-dialog,\
+                     public.accessibility.describes-music-and-sound\",URI=\"subs/en.m3u8\""
+            .parse::<ExtXMedia>()
+            .unwrap();
+
+        assert!(media.is_accessibility());
+
+        let media = "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",\
+                     LANGUAGE=\"en\",URI=\"en/prog_index.m3u8\""
+            .parse::<ExtXMedia>()
+            .unwrap();
+
+        assert!(!media.is_accessibility());
+
+        let mut media = ExtXMedia::new(MediaType::Audio, "aac", "English");
+        media.set_characteristics(Some("com.example.commentary"));
+
+        assert!(!media.is_accessibility());
+    }
+
     #[test]
     fn test_parser_error() {
         assert!("".parse::<ExtXMedia>().is_err());
@@ -1,9 +1,11 @@
+mod content_steering;
 mod i_frame_stream_inf;
 mod media;
 mod session_data;
 mod session_key;
 mod stream_inf;
 
+pub use content_steering::*;
 pub use i_frame_stream_inf::*;
 pub use media::*;
 pub use session_data::*;
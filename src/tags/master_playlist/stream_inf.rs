@@ -4,10 +4,11 @@ use std::str::FromStr;
 
 use crate::attribute::AttributePairs;
 use crate::types::{
-    ClosedCaptions, DecimalFloatingPoint, HdcpLevel, ProtocolVersion, StreamInf, StreamInfBuilder,
+    ClosedCaptions, DecimalFloatingPoint, HdcpLevel, ProtocolVersion, ResolvedVariant, StreamInf,
+    StreamInfBuilder,
 };
 use crate::utils::{quote, tag, unquote};
-use crate::{Error, RequiredVersion};
+use crate::{Error, MediaPlaylist, RequiredVersion};
 
 /// # [4.3.4.2. EXT-X-STREAM-INF]
 ///
@@ -113,8 +114,19 @@ impl ExtXStreamInfBuilder {
         self
     }
 
+    /// The content steering pathway, that the stream belongs to.
+    pub fn pathway_id<T: Into<String>>(&mut self, value: T) -> &mut Self {
+        self.stream_inf.pathway_id(value);
+        self
+    }
+
     /// Build an [`ExtXStreamInf`].
     pub fn build(&self) -> crate::Result<ExtXStreamInf> {
+        // `BANDWIDTH` is the only attribute without a default value:
+        if !self.stream_inf.has_bandwidth() {
+            return Err(Error::missing_attribute("BANDWIDTH"));
+        }
+
         Ok(ExtXStreamInf {
             uri: self
                 .uri
@@ -298,6 +310,36 @@ impl ExtXStreamInf {
         self.closed_captions = value;
         self
     }
+
+    /// Bundles the variant stream with the [`MediaPlaylist`], that has been
+    /// fetched from its `URI`.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXStreamInf;
+    /// use hls_m3u8::MediaPlaylist;
+    ///
+    /// let stream = ExtXStreamInf::new("http://example.com/low/index.m3u8", 1_280_000);
+    /// let media_playlist = "#EXTM3U\n\
+    ///                       #EXT-X-TARGETDURATION:10\n\
+    ///                       #EXTINF:10,\n\
+    ///                       http://media.example.com/first.ts"
+    ///     .parse::<MediaPlaylist>()?;
+    ///
+    /// let variant = stream.with_media_playlist(media_playlist);
+    ///
+    /// assert_eq!(variant.bandwidth(), 1_280_000);
+    /// assert_eq!(variant.segment_count(), 1);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    pub const fn with_media_playlist(self, media_playlist: MediaPlaylist) -> ResolvedVariant {
+        ResolvedVariant {
+            stream_inf: self,
+            media_playlist,
+        }
+    }
 }
 
 /// This tag requires [`ProtocolVersion::V1`].
@@ -390,6 +432,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_missing_bandwidth() {
+        assert_eq!(
+            "#EXT-X-STREAM-INF:AVERAGE-BANDWIDTH=1000\nhttp://www.example.com"
+                .parse::<ExtXStreamInf>()
+                .unwrap_err()
+                .to_string(),
+            "Missing Attribute: BANDWIDTH".to_string()
+        );
+
+        assert_eq!(
+            ExtXStreamInf::builder()
+                .uri("http://www.example.com")
+                .average_bandwidth(1000)
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "Missing Attribute: BANDWIDTH".to_string()
+        );
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -398,6 +461,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_with_media_playlist() {
+        let media_playlist = "#EXTM3U\n\
+                              #EXT-X-TARGETDURATION:10\n\
+                              #EXTINF:10,\n\
+                              http://media.example.com/first.ts\n\
+                              #EXTINF:4.5,\n\
+                              http://media.example.com/second.ts\n\
+                              #EXT-X-ENDLIST"
+            .parse::<MediaPlaylist>()
+            .unwrap();
+
+        let mut stream_inf = ExtXStreamInf::new("http://example.com/low/index.m3u8", 1_280_000);
+        stream_inf.set_average_bandwidth(Some(1_000_000));
+
+        let variant = stream_inf
+            .clone()
+            .with_media_playlist(media_playlist.clone());
+
+        assert_eq!(variant.bandwidth(), 1_280_000);
+        assert_eq!(variant.stream_inf().average_bandwidth(), Some(1_000_000));
+        assert_eq!(variant.uri(), "http://example.com/low/index.m3u8");
+        assert_eq!(variant.segment_count(), 2);
+        assert_eq!(variant.duration(), std::time::Duration::from_millis(14500));
+        assert_eq!(variant.media_playlist(), &media_playlist);
+        assert_eq!(variant.into_parts(), (stream_inf, media_playlist));
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(
@@ -50,7 +50,8 @@ impl FromStr for ExtXPlaylistType {
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let input = tag(input, Self::PREFIX)?;
-        match input {
+        // some encoders write the playlist type in lowercase:
+        match input.to_ascii_uppercase().as_str() {
             "EVENT" => Ok(Self::Event),
             "VOD" => Ok(Self::Vod),
             _ => Err(Error::custom(format!("Unknown playlist type: {:?}", input))),
@@ -86,6 +87,29 @@ mod test {
         assert!("garbage".parse::<ExtXPlaylistType>().is_err());
     }
 
+    #[test]
+    fn test_parser_case_insensitive() {
+        let playlist_type = "#EXT-X-PLAYLIST-TYPE:vod"
+            .parse::<ExtXPlaylistType>()
+            .unwrap();
+
+        assert_eq!(playlist_type, ExtXPlaylistType::Vod);
+        assert_eq!(
+            playlist_type.to_string(),
+            "#EXT-X-PLAYLIST-TYPE:VOD".to_string()
+        );
+
+        let playlist_type = "#EXT-X-PLAYLIST-TYPE:Event"
+            .parse::<ExtXPlaylistType>()
+            .unwrap();
+
+        assert_eq!(playlist_type, ExtXPlaylistType::Event);
+        assert_eq!(
+            playlist_type.to_string(),
+            "#EXT-X-PLAYLIST-TYPE:EVENT".to_string()
+        );
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::types::ProtocolVersion;
+use crate::utils::tag;
+use crate::{Error, RequiredVersion};
+
+/// # [4.4.4.8. EXT-X-BITRATE]
+/// The [`ExtXBitrate`] tag identifies the approximate segment bit rate of the
+/// [`Media Segment`]s that follow it.
+///
+/// It applies to every [`Media Segment`] between it and the next
+/// [`ExtXBitrate`] tag in the Playlist file (or the end of the Playlist file).
+///
+/// Its format is:
+/// ```text
+/// #EXT-X-BITRATE:<rate>
+/// ```
+/// where `rate` is the bit rate in kilobits per second.
+///
+/// [`Media Segment`]: crate::MediaSegment
+/// [4.4.4.8. EXT-X-BITRATE]:
+/// https://tools.ietf.org/html/draft-pantos-hls-rfc8216bis-07#section-4.4.4.8
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct ExtXBitrate(u64);
+
+impl ExtXBitrate {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-BITRATE:";
+
+    /// Makes a new [`ExtXBitrate`] tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXBitrate;
+    /// let bitrate = ExtXBitrate::new(4500);
+    /// ```
+    pub const fn new(bitrate: u64) -> Self { Self(bitrate) }
+
+    /// Returns the bit rate in kilobits per second.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXBitrate;
+    /// let bitrate = ExtXBitrate::new(4500);
+    ///
+    /// assert_eq!(bitrate.bitrate(), 4500);
+    /// ```
+    pub const fn bitrate(self) -> u64 { self.0 }
+
+    /// Sets the bit rate in kilobits per second.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXBitrate;
+    /// let mut bitrate = ExtXBitrate::new(4500);
+    ///
+    /// bitrate.set_bitrate(1200);
+    /// assert_eq!(bitrate.bitrate(), 1200);
+    /// ```
+    pub fn set_bitrate(&mut self, value: u64) -> &mut Self {
+        self.0 = value;
+        self
+    }
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl RequiredVersion for ExtXBitrate {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXBitrate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}{}", Self::PREFIX, self.0) }
+}
+
+impl FromStr for ExtXBitrate {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let bitrate = tag(input, Self::PREFIX)?.parse()?;
+        Ok(Self::new(bitrate))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXBitrate::new(4500).to_string(),
+            "#EXT-X-BITRATE:4500".to_string()
+        );
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXBitrate::new(4500).required_version(),
+            ProtocolVersion::V1
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXBitrate::new(4500),
+            "#EXT-X-BITRATE:4500".parse().unwrap()
+        );
+
+        assert!("#EXT-X-BITRATE:".parse::<ExtXBitrate>().is_err());
+        assert!("#EXT-X-BITRATE:garbage".parse::<ExtXBitrate>().is_err());
+    }
+
+    #[test]
+    fn test_bitrate() {
+        let mut bitrate = ExtXBitrate::new(4500);
+        assert_eq!(bitrate.bitrate(), 4500);
+        bitrate.set_bitrate(1200);
+        assert_eq!(bitrate.bitrate(), 1200);
+    }
+}
@@ -688,6 +688,85 @@ impl ExtXDateRange {
         self.client_attributes = value;
         self
     }
+
+    /// Converts the [`ExtXDateRange`] into a [`DateRangeEvent`].
+    ///
+    /// The end of the event is the `END-DATE` or, if it is missing, the
+    /// `START-DATE` plus the `DURATION`. The title is taken from the
+    /// `X-TITLE` client attribute, if it is a string.
+    ///
+    /// Returns `None`, if the end of the event can not be resolved.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use chrono::offset::TimeZone;
+    /// use chrono::FixedOffset;
+    /// use std::time::Duration;
+    ///
+    /// let start_date = FixedOffset::east(0).ymd(2010, 2, 19).and_hms(14, 0, 0);
+    /// let mut date_range = ExtXDateRange::new("id", start_date);
+    /// assert_eq!(date_range.as_event(), None);
+    ///
+    /// date_range.set_duration(Some(Duration::from_secs(3600)));
+    ///
+    /// let event = date_range.as_event().unwrap();
+    /// assert_eq!(event.start(), start_date);
+    /// assert_eq!(
+    ///     event.end(),
+    ///     FixedOffset::east(0).ymd(2010, 2, 19).and_hms(15, 0, 0)
+    /// );
+    /// ```
+    pub fn as_event(&self) -> Option<DateRangeEvent> {
+        let end = {
+            if let Some(end_date) = self.end_date {
+                end_date
+            } else {
+                let duration = chrono::Duration::from_std(self.duration?).ok()?;
+                self.start_date.checked_add_signed(duration)?
+            }
+        };
+
+        let title = {
+            if let Some(Value::String(title)) = self.client_attributes.get("X-TITLE") {
+                Some(title.clone())
+            } else {
+                None
+            }
+        };
+
+        Some(DateRangeEvent {
+            id: self.id.clone(),
+            start: self.start_date,
+            end,
+            title,
+        })
+    }
+}
+
+/// A bounded event, that is described by an [`ExtXDateRange`].
+///
+/// See [`ExtXDateRange::as_event`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DateRangeEvent {
+    id: String,
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+    title: Option<String>,
+}
+
+impl DateRangeEvent {
+    /// Returns the `ID` of the [`ExtXDateRange`].
+    pub const fn id(&self) -> &String { &self.id }
+
+    /// Returns the start of the event.
+    pub const fn start(&self) -> DateTime<FixedOffset> { self.start }
+
+    /// Returns the end of the event.
+    pub const fn end(&self) -> DateTime<FixedOffset> { self.end }
+
+    /// Returns the title of the event.
+    pub const fn title(&self) -> &Option<String> { &self.title }
 }
 
 /// This tag requires [`ProtocolVersion::V1`].
@@ -952,4 +1031,54 @@ mod test {
             ProtocolVersion::V1
         );
     }
+
+    #[test]
+    fn test_as_event() {
+        let date_range = "#EXT-X-DATERANGE:\
+                          ID=\"show-1\",\
+                          START-DATE=\"2014-03-05T11:00:00Z\",\
+                          DURATION=1800,\
+                          X-TITLE=\"Morning News\""
+            .parse::<ExtXDateRange>()
+            .unwrap();
+
+        let event = date_range.as_event().unwrap();
+
+        assert_eq!(event.id(), &"show-1".to_string());
+        assert_eq!(
+            event.start(),
+            FixedOffset::east(0).ymd(2014, 3, 5).and_hms(11, 0, 0)
+        );
+        assert_eq!(
+            event.end(),
+            FixedOffset::east(0).ymd(2014, 3, 5).and_hms(11, 30, 0)
+        );
+        assert_eq!(event.title(), &Some("Morning News".to_string()));
+
+        // the END-DATE takes precedence:
+        let date_range = "#EXT-X-DATERANGE:\
+                          ID=\"show-2\",\
+                          START-DATE=\"2014-03-05T11:30:00Z\",\
+                          END-DATE=\"2014-03-05T12:00:00Z\""
+            .parse::<ExtXDateRange>()
+            .unwrap();
+
+        let event = date_range.as_event().unwrap();
+
+        assert_eq!(
+            event.end(),
+            FixedOffset::east(0).ymd(2014, 3, 5).and_hms(12, 0, 0)
+        );
+        assert_eq!(event.title(), &None);
+
+        // an open ended date range can not be converted:
+        assert_eq!(
+            ExtXDateRange::new(
+                "show-3",
+                FixedOffset::east(0).ymd(2014, 3, 5).and_hms(12, 0, 0)
+            )
+            .as_event(),
+            None
+        );
+    }
 }
@@ -67,6 +67,24 @@ impl ExtInf {
     /// ```
     pub const fn duration(&self) -> Duration { self.duration }
 
+    /// Returns the duration of the associated media segment in whole
+    /// milliseconds.
+    ///
+    /// # Note
+    /// The duration is rounded to the nearest millisecond, so the imprecision
+    /// of the parsed floating point value does not leak into the result
+    /// (`9.009` is stored as `9.008999999` seconds, but returns `9009`).
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::tags::ExtInf;
+    /// let ext_inf = "#EXTINF:9.009,".parse::<ExtInf>()?;
+    ///
+    /// assert_eq!(ext_inf.duration_millis(), 9009);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub const fn duration_millis(&self) -> u128 { (self.duration.as_nanos() + 500_000) / 1_000_000 }
+
     /// Sets the duration of the associated media segment.
     ///
     /// # Example
@@ -232,6 +250,26 @@ mod test {
         assert!("#EXTINF:garbage".parse::<ExtInf>().is_err());
     }
 
+    #[test]
+    fn test_duration_millis() {
+        for (input, millis) in &[
+            ("#EXTINF:9.009,", 9009),
+            ("#EXTINF:3.003,", 3003),
+            ("#EXTINF:0.1,", 100),
+            ("#EXTINF:6.006,", 6006),
+            ("#EXTINF:10.0,", 10000),
+            ("#EXTINF:5220,", 5_220_000),
+            ("#EXTINF:4.0005,", 4001),
+        ] {
+            assert_eq!(input.parse::<ExtInf>().unwrap().duration_millis(), *millis);
+        }
+
+        assert_eq!(
+            ExtInf::new(Duration::from_secs_f64(9.009)).duration_millis(),
+            9009
+        );
+    }
+
     #[test]
     fn test_title() {
         assert_eq!(ExtInf::new(Duration::from_secs(5)).title(), &None);
@@ -2,6 +2,7 @@ use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
+use crate::tags::ExtXSessionKey;
 use crate::types::{DecryptionKey, EncryptionMethod, ProtocolVersion};
 use crate::utils::tag;
 use crate::{Error, RequiredVersion};
@@ -99,6 +100,27 @@ impl fmt::Display for ExtXKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}{}", Self::PREFIX, self.0) }
 }
 
+/// Converts an [`ExtXSessionKey`] into the equivalent [`ExtXKey`], which
+/// allows to reuse a key, that has been preloaded from a [`Master Playlist`].
+///
+/// # Example
+/// ```
+/// # use hls_m3u8::tags::{ExtXKey, ExtXSessionKey};
+/// use hls_m3u8::types::EncryptionMethod;
+///
+/// let session_key = ExtXSessionKey::new(EncryptionMethod::Aes128, "https://www.example.com/");
+///
+/// assert_eq!(
+///     ExtXKey::from(session_key),
+///     ExtXKey::new(EncryptionMethod::Aes128, "https://www.example.com/")
+/// );
+/// ```
+///
+/// [`Master Playlist`]: crate::MasterPlaylist
+impl From<ExtXSessionKey> for ExtXKey {
+    fn from(value: ExtXSessionKey) -> Self { Self(value.deref().clone()) }
+}
+
 impl Deref for ExtXKey {
     type Target = DecryptionKey;
 
@@ -156,4 +178,72 @@ mod test {
             16, 239, 143, 117, 140, 165, 85, 17, 85, 132, 187, 91, 60, 104, 127, 82,
         ]));
     }
+
+    #[test]
+    fn test_data_uri() {
+        let key = "#EXT-X-KEY:METHOD=AES-128,\
+                   URI=\"data:text/plain;charset=utf-8;base64,AAECAwQFBgcICQoLDA0ODw==\""
+            .parse::<ExtXKey>()
+            .unwrap();
+
+        assert_eq!(
+            key.uri(),
+            &Some("data:text/plain;charset=utf-8;base64,AAECAwQFBgcICQoLDA0ODw==".to_string())
+        );
+        assert_eq!(
+            key.data_uri_bytes().unwrap(),
+            Some(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15])
+        );
+        assert_eq!(
+            key.to_string(),
+            "#EXT-X-KEY:METHOD=AES-128,\
+             URI=\"data:text/plain;charset=utf-8;base64,AAECAwQFBgcICQoLDA0ODw==\""
+                .to_string()
+        );
+
+        assert_eq!(
+            ExtXKey::new(EncryptionMethod::Aes128, "https://www.example.com/key")
+                .data_uri_bytes()
+                .unwrap(),
+            None
+        );
+        assert!(ExtXKey::new(EncryptionMethod::Aes128, "data:;base64,!!!")
+            .data_uri_bytes()
+            .is_err());
+
+        // the fifth byte is not a char boundary:
+        let key = "#EXT-X-KEY:METHOD=AES-128,URI=\"abcd€xyz\""
+            .parse::<ExtXKey>()
+            .unwrap();
+
+        assert_eq!(key.data_uri_bytes().unwrap(), None);
+    }
+
+    #[test]
+    fn test_from_session_key() {
+        let mut session_key = ExtXSessionKey::new(
+            EncryptionMethod::SampleAes,
+            "https://www.example.com/hls-key/key.bin",
+        );
+        session_key
+            .set_key_format(Some(KeyFormat::Identity))
+            .set_key_format_versions(Some(vec![1, 2]));
+
+        let key = ExtXKey::from(session_key);
+
+        assert_eq!(key.method(), EncryptionMethod::SampleAes);
+        assert_eq!(
+            key.uri(),
+            &Some("https://www.example.com/hls-key/key.bin".to_string())
+        );
+        assert_eq!(key.key_format(), Some(KeyFormat::Identity));
+        assert_eq!(
+            key.to_string(),
+            "#EXT-X-KEY:METHOD=SAMPLE-AES,\
+             URI=\"https://www.example.com/hls-key/key.bin\",\
+             KEYFORMAT=\"identity\",\
+             KEYFORMATVERSIONS=\"1/2\""
+                .to_string()
+        );
+    }
 }
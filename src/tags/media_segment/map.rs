@@ -12,7 +12,12 @@ use crate::{Encrypted, Error, RequiredVersion};
 /// The [`ExtXMap`] tag specifies how to obtain the Media Initialization
 /// Section, required to parse the applicable [`MediaSegment`]s.
 ///
+/// If the Media Initialization Section is encrypted, the keys, that are used
+/// to decrypt it, are inherited from the preceding [`ExtXKey`] tags and can be
+/// accessed through the [`Encrypted`] trait.
+///
 /// [`MediaSegment`]: crate::MediaSegment
+/// [`Encrypted`]: crate::Encrypted
 /// [4.3.2.5. EXT-X-MAP]: https://tools.ietf.org/html/rfc8216#section-4.3.2.5
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ExtXMap {
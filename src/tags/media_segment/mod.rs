@@ -1,3 +1,4 @@
+mod bitrate;
 mod byte_range;
 mod date_range;
 mod discontinuity;
@@ -6,6 +7,7 @@ mod key;
 mod map;
 mod program_date_time;
 
+pub use bitrate::*;
 pub use byte_range::*;
 pub use date_range::*;
 pub use discontinuity::*;
@@ -49,6 +49,14 @@ use crate::types::{EncryptionMethod, ProtocolVersion};
 ///
 /// assert!(example_tag.is_encrypted());
 /// assert!(!example_tag.is_not_encrypted());
+///
+/// assert_eq!(
+///     example_tag.decryption_info(),
+///     Some(&ExtXKey::new(
+///         EncryptionMethod::Aes128,
+///         "http://www.example.com/data.bin"
+///     ))
+/// );
 /// ```
 pub trait Encrypted {
     /// Returns a shared reference to all keys, that can be used to decrypt this
@@ -96,6 +104,16 @@ pub trait Encrypted {
     ///
     /// [`is_encrypted`]: #method.is_encrypted
     fn is_not_encrypted(&self) -> bool { !self.is_encrypted() }
+
+    /// Returns the first key, that can be used to decrypt this tag, or `None`,
+    /// if the tag is not encrypted.
+    ///
+    /// Keys with [`EncryptionMethod::None`] are skipped.
+    fn decryption_info(&self) -> Option<&ExtXKey> {
+        self.keys()
+            .iter()
+            .find(|k| k.method() != EncryptionMethod::None)
+    }
 }
 
 /// # Example
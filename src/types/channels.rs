@@ -80,7 +80,13 @@ impl FromStr for Channels {
         let channel_number = parameters
             .first()
             .ok_or_else(|| Error::missing_attribute("First parameter of channels!"))?
-            .parse()?;
+            .parse::<u64>()
+            .map_err(|_| Error::invalid_input())?;
+
+        // the count of audio channels has to be a positive integer
+        if channel_number == 0 {
+            return Err(Error::invalid_input());
+        }
 
         Ok(Self {
             channel_number,
@@ -93,7 +99,7 @@ impl fmt::Display for Channels {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.channel_number)?;
         if !self.unknown.is_empty() {
-            write!(f, "{}", self.unknown.join(","))?;
+            write!(f, "/{}", self.unknown.join("/"))?;
         }
 
         Ok(())
@@ -120,4 +126,23 @@ mod tests {
         assert!("garbage".parse::<Channels>().is_err());
         assert!("".parse::<Channels>().is_err());
     }
+
+    #[test]
+    fn test_parser_channel_number() {
+        assert_eq!("2".parse::<Channels>().unwrap(), Channels::new(2));
+
+        let channels = "16/JOC".parse::<Channels>().unwrap();
+        assert_eq!(channels.channel_number(), 16);
+        assert_eq!(channels.to_string(), "16/JOC".to_string());
+
+        assert_eq!(
+            "0".parse::<Channels>().unwrap_err().to_string(),
+            Error::invalid_input().to_string()
+        );
+        assert_eq!(
+            "abc".parse::<Channels>().unwrap_err().to_string(),
+            Error::invalid_input().to_string()
+        );
+        assert!("0/JOC".parse::<Channels>().is_err());
+    }
 }
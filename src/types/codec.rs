@@ -0,0 +1,170 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// A single entry of a `CODECS` attribute, as described in [RFC6381].
+///
+/// The codec is split into its family (the sample entry, like `avc1` or
+/// `mp4a`) and the dot separated parameters, that follow it. The parameters
+/// are kept as they are, so families, that are not known by this crate, are
+/// still preserved.
+///
+/// # Example
+/// ```
+/// # use hls_m3u8::types::Codec;
+/// let codec = "avc1.4d401e".parse::<Codec>()?;
+///
+/// assert_eq!(codec.family(), "avc1");
+/// assert_eq!(codec.parameters(), &["4d401e".to_string()]);
+/// assert_eq!(codec.avc_profile_level(), Some((77, 30)));
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+///
+/// [RFC6381]: https://tools.ietf.org/html/rfc6381#section-3.3
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Codec {
+    family: String,
+    parameters: Vec<String>,
+}
+
+impl Codec {
+    /// Returns the codec family (e.g. `avc1`, `hvc1` or `mp4a`).
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::types::Codec;
+    /// let codec = "mp4a.40.2".parse::<Codec>()?;
+    ///
+    /// assert_eq!(codec.family(), "mp4a");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn family(&self) -> &str { &self.family }
+
+    /// Returns the parameters, that follow the codec family.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::types::Codec;
+    /// let codec = "mp4a.40.2".parse::<Codec>()?;
+    ///
+    /// assert_eq!(codec.parameters(), &["40".to_string(), "2".to_string()]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn parameters(&self) -> &[String] { &self.parameters }
+
+    /// Returns the `profile_idc` and `level_idc` of an AVC (`avc1` or `avc3`)
+    /// codec.
+    ///
+    /// Returns `None`, if this is not an AVC codec or the parameters are
+    /// malformed.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::types::Codec;
+    /// let codec = "avc1.640028".parse::<Codec>()?;
+    ///
+    /// // High Profile, Level 4.0
+    /// assert_eq!(codec.avc_profile_level(), Some((100, 40)));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn avc_profile_level(&self) -> Option<(u8, u8)> {
+        if self.family != "avc1" && self.family != "avc3" {
+            return None;
+        }
+
+        let value = self.parameters.first()?;
+        if value.len() != 6 {
+            return None;
+        }
+
+        let profile = u8::from_str_radix(value.get(0..2)?, 16).ok()?;
+        let level = u8::from_str_radix(value.get(4..6)?, 16).ok()?;
+
+        Some((profile, level))
+    }
+}
+
+impl FromStr for Codec {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parts = input.trim().split('.');
+
+        let family = parts
+            .next()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| Error::custom(format!("invalid codec: {:?}", input)))?;
+
+        Ok(Self {
+            family: family.to_string(),
+            parameters: parts.map(|v| v.to_string()).collect(),
+        })
+    }
+}
+
+impl fmt::Display for Codec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.family)?;
+
+        for parameter in &self.parameters {
+            write!(f, ".{}", parameter)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "avc1.4d401e".parse::<Codec>().unwrap().to_string(),
+            "avc1.4d401e".to_string()
+        );
+        assert_eq!(
+            "hvc1.2.4.L123.B0".parse::<Codec>().unwrap().to_string(),
+            "hvc1.2.4.L123.B0".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        let codec = "avc1.4d401e".parse::<Codec>().unwrap();
+        assert_eq!(codec.family(), "avc1");
+        assert_eq!(codec.parameters(), &["4d401e".to_string()]);
+        assert_eq!(codec.avc_profile_level(), Some((77, 30)));
+
+        let codec = "hvc1.2.4.L123.B0".parse::<Codec>().unwrap();
+        assert_eq!(codec.family(), "hvc1");
+        assert_eq!(
+            codec.parameters(),
+            &[
+                "2".to_string(),
+                "4".to_string(),
+                "L123".to_string(),
+                "B0".to_string()
+            ]
+        );
+        assert_eq!(codec.avc_profile_level(), None);
+
+        let codec = " mp4a.40.2".parse::<Codec>().unwrap();
+        assert_eq!(codec.family(), "mp4a");
+        assert_eq!(codec.parameters(), &["40".to_string(), "2".to_string()]);
+
+        let codec = "ac-3".parse::<Codec>().unwrap();
+        assert_eq!(codec.family(), "ac-3");
+        assert!(codec.parameters().is_empty());
+
+        assert_eq!(
+            "avc1.garbage".parse::<Codec>().unwrap().avc_profile_level(),
+            None
+        );
+        assert!("".parse::<Codec>().is_err());
+        assert!(".40.2".parse::<Codec>().is_err());
+    }
+}
@@ -25,6 +25,9 @@ pub struct DecryptionKey {
     pub(crate) uri: Option<String>,
     #[builder(setter(into, strip_option), default)]
     /// The IV (Initialization Vector) attribute.
+    ///
+    /// Both [`EncryptionMethod::Aes128`] and [`EncryptionMethod::SampleAes`]
+    /// require a 128-bit IV, so parsing an IV of any other length fails.
     pub(crate) iv: Option<InitializationVector>,
     #[builder(setter(into, strip_option), default)]
     /// A string that specifies how the key is
@@ -269,6 +272,58 @@ impl DecryptionKey {
         self.key_format_versions = value.map(Into::into);
         self
     }
+
+    /// Decodes the key, if the `URI` is a base64 encoded `data:` URI
+    /// ([RFC 2397]).
+    ///
+    /// Returns `Ok(None)`, if the `URI` is missing or not a base64 encoded
+    /// `data:` URI.
+    ///
+    /// # Example
+    /// ```
+    /// # use hls_m3u8::types::DecryptionKey;
+    /// use hls_m3u8::types::EncryptionMethod;
+    ///
+    /// let key = DecryptionKey::new(
+    ///     EncryptionMethod::Aes128,
+    ///     "data:text/plain;base64,AAECAwQFBgcICQoLDA0ODw==",
+    /// );
+    ///
+    /// assert_eq!(
+    ///     key.data_uri_bytes().unwrap(),
+    ///     Some(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15])
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the payload of the `data:` URI is not valid
+    /// base64.
+    ///
+    /// [RFC 2397]: https://tools.ietf.org/html/rfc2397
+    pub fn data_uri_bytes(&self) -> crate::Result<Option<Vec<u8>>> {
+        let uri = {
+            if let Some(uri) = &self.uri {
+                uri.trim()
+            } else {
+                return Ok(None);
+            }
+        };
+
+        if uri.len() < 5 || !uri.as_bytes()[..5].eq_ignore_ascii_case(b"data:") {
+            return Ok(None);
+        }
+
+        let mut parts = uri[5..].splitn(2, ',');
+        let header = parts.next().unwrap_or_default();
+
+        match parts.next() {
+            Some(data) if header.to_ascii_lowercase().ends_with(";base64") => {
+                Ok(Some(::base64::decode(data)?))
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 impl RequiredVersion for DecryptionKey {
@@ -386,6 +441,36 @@ mod test {
             .is_err());
     }
 
+    #[test]
+    fn test_parser_iv_length() {
+        let mut key = DecryptionKey::new(EncryptionMethod::Aes128, "https://www.example.com/");
+        key.set_iv(Some([
+            16, 239, 143, 117, 140, 165, 85, 17, 85, 132, 187, 91, 60, 104, 127, 82,
+        ]));
+
+        assert_eq!(
+            "METHOD=AES-128,\
+             URI=\"https://www.example.com/\",\
+             IV=0x10ef8f758ca555115584bb5b3c687f52"
+                .parse::<DecryptionKey>()
+                .unwrap(),
+            key
+        );
+
+        // a 15-byte IV:
+        assert!("METHOD=AES-128,\
+                 URI=\"https://www.example.com/\",\
+                 IV=0x10ef8f758ca555115584bb5b3c687f"
+            .parse::<DecryptionKey>()
+            .is_err());
+
+        assert!("METHOD=SAMPLE-AES,\
+                 URI=\"https://www.example.com/\",\
+                 IV=0x10ef8f758ca555115584bb5b3c687f5201"
+            .parse::<DecryptionKey>()
+            .is_err());
+    }
+
     #[test]
     fn test_display() {
         let mut key = DecryptionKey::new(
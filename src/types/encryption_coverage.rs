@@ -0,0 +1,40 @@
+use std::collections::BTreeSet;
+
+use crate::types::EncryptionMethod;
+
+/// A report about the encryption of the [`MediaSegment`]s in a
+/// [`MediaPlaylist`].
+///
+/// See [`MediaPlaylist::encryption_coverage`].
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+/// [`MediaPlaylist::encryption_coverage`]:
+/// crate::MediaPlaylist::encryption_coverage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EncryptionCoverage {
+    pub(crate) encrypted_segments: usize,
+    pub(crate) clear_segments: usize,
+    pub(crate) methods: BTreeSet<EncryptionMethod>,
+    pub(crate) has_clear_gap: bool,
+}
+
+impl EncryptionCoverage {
+    /// Returns the number of encrypted segments.
+    pub const fn encrypted_segments(&self) -> usize { self.encrypted_segments }
+
+    /// Returns the number of segments, that are not encrypted.
+    pub const fn clear_segments(&self) -> usize { self.clear_segments }
+
+    /// Returns all [`EncryptionMethod`]s, that are used to encrypt segments.
+    ///
+    /// [`EncryptionMethod::None`] is never part of this set.
+    pub const fn methods(&self) -> &BTreeSet<EncryptionMethod> { &self.methods }
+
+    /// Returns `true`, if a segment, that is not encrypted, appears between
+    /// two encrypted segments.
+    pub const fn has_clear_gap(&self) -> bool { self.has_clear_gap }
+
+    /// Returns `true`, if every segment is encrypted.
+    pub const fn is_fully_encrypted(&self) -> bool { self.clear_segments == 0 }
+}
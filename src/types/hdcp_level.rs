@@ -2,6 +2,9 @@ use strum::{Display, EnumString};
 
 /// HDCP level.
 ///
+/// The levels are ordered by increasing protection (`NONE` < `TYPE-0` <
+/// `TYPE-1`).
+///
 /// See: [4.3.4.2. EXT-X-STREAM-INF]
 ///
 /// [4.3.4.2. EXT-X-STREAM-INF]: https://tools.ietf.org/html/rfc8216#section-4.3.4.2
@@ -9,9 +12,11 @@ use strum::{Display, EnumString};
 #[derive(Ord, PartialOrd, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
 #[strum(serialize_all = "SCREAMING-KEBAB-CASE")]
 pub enum HdcpLevel {
+    None,
     #[strum(serialize = "TYPE-0")]
     Type0,
-    None,
+    #[strum(serialize = "TYPE-1")]
+    Type1,
 }
 
 #[cfg(test)]
@@ -26,6 +31,9 @@ mod tests {
 
         let level = HdcpLevel::None;
         assert_eq!(level.to_string(), "NONE".to_string());
+
+        let level = HdcpLevel::Type1;
+        assert_eq!(level.to_string(), "TYPE-1".to_string());
     }
 
     #[test]
@@ -36,6 +44,15 @@ mod tests {
         let level = HdcpLevel::None;
         assert_eq!(level, "NONE".parse::<HdcpLevel>().unwrap());
 
+        let level = HdcpLevel::Type1;
+        assert_eq!(level, "TYPE-1".parse::<HdcpLevel>().unwrap());
+
         assert!("unk".parse::<HdcpLevel>().is_err());
     }
+
+    #[test]
+    fn test_ord() {
+        assert!(HdcpLevel::None < HdcpLevel::Type0);
+        assert!(HdcpLevel::Type0 < HdcpLevel::Type1);
+    }
 }
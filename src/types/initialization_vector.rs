@@ -48,8 +48,13 @@ impl FromStr for InitializationVector {
         if !(input.starts_with("0x") || input.starts_with("0X")) {
             return Err(Error::invalid_input());
         }
+        // AES-128 and SAMPLE-AES both require a 128-bit IV
         if input.len() - 2 != 32 {
-            return Err(Error::invalid_input());
+            return Err(Error::custom(format!(
+                "An IV has to be 16 bytes long, but got {} hex digits: {:?}",
+                input.len() - 2,
+                input
+            )));
         }
 
         let mut result = [0; 16];
@@ -111,6 +116,15 @@ mod tests {
         assert!("garbage".parse::<InitializationVector>().is_err());
         assert!("0xgarbage".parse::<InitializationVector>().is_err());
         assert!("0x12".parse::<InitializationVector>().is_err());
+        assert_eq!(
+            "0x10ef8f758ca555115584bb5b3c687f"
+                .parse::<InitializationVector>()
+                .unwrap_err()
+                .to_string(),
+            "CustomError: An IV has to be 16 bytes long, but got 30 hex digits: \
+             \"0x10ef8f758ca555115584bb5b3c687f\""
+                .to_string()
+        );
         assert!("0X10EF8F758CA555115584BB5B3C687F5Z"
             .parse::<InitializationVector>()
             .is_err());
@@ -0,0 +1,97 @@
+use strum::{Display, EnumString};
+
+/// A non-fatal deviation from the recommendations of the specification, that
+/// has been found in a [`MediaPlaylist`].
+///
+/// See [`MediaPlaylist::lint`].
+///
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+/// [`MediaPlaylist::lint`]: crate::MediaPlaylist::lint
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lint {
+    pub(crate) code: LintCode,
+    pub(crate) message: String,
+}
+
+impl Lint {
+    pub(crate) fn new<T: ToString>(code: LintCode, message: T) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the [`LintCode`], that identifies the kind of the deviation.
+    pub const fn code(&self) -> LintCode { self.code }
+
+    /// Returns a human readable description of the deviation.
+    pub const fn message(&self) -> &String { &self.message }
+}
+
+/// The kind of a [`Lint`].
+///
+/// The codes are written in kebab case (for example
+/// `missing-playlist-type`).
+#[derive(Ord, PartialOrd, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "kebab_case")]
+pub enum LintCode {
+    /// The playlist has an [`ExtXEndList`] tag, but no [`ExtXPlaylistType`]
+    /// tag, so clients can not know, that the playlist will not change.
+    ///
+    /// [`ExtXEndList`]: crate::tags::ExtXEndList
+    /// [`ExtXPlaylistType`]: crate::tags::ExtXPlaylistType
+    MissingPlaylistType,
+    /// A [`MediaSegment`] has more than one [`ExtXKey`] with the same
+    /// `KEYFORMAT`, so only the last one of them applies, or it is preceded
+    /// by an [`ExtXKey`] tag, that is equal to the key already in effect.
+    ///
+    /// The latter can only be found in the document, so it is only reported
+    /// by [`MediaPlaylistBuilder::parse_with_lints`].
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    /// [`ExtXKey`]: crate::tags::ExtXKey
+    /// [`MediaPlaylistBuilder::parse_with_lints`]:
+    /// crate::MediaPlaylistBuilder::parse_with_lints
+    RedundantKey,
+    /// The [`ExtXTargetDuration`] of a live playlist (a playlist without an
+    /// [`ExtXEndList`] tag, that is not a [`ExtXPlaylistType::Vod`] playlist)
+    /// is more than one second larger than the longest rounded segment
+    /// duration, which makes clients reload the playlist less often than
+    /// necessary.
+    ///
+    /// [`ExtXTargetDuration`]: crate::tags::ExtXTargetDuration
+    /// [`ExtXEndList`]: crate::tags::ExtXEndList
+    /// [`ExtXPlaylistType::Vod`]: crate::tags::ExtXPlaylistType::Vod
+    LargeTargetDuration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            LintCode::MissingPlaylistType.to_string(),
+            "missing-playlist-type".to_string()
+        );
+        assert_eq!(
+            LintCode::RedundantKey.to_string(),
+            "redundant-key".to_string()
+        );
+        assert_eq!(
+            LintCode::LargeTargetDuration.to_string(),
+            "large-target-duration".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            "redundant-key".parse::<LintCode>().unwrap(),
+            LintCode::RedundantKey
+        );
+        assert!("garbage".parse::<LintCode>().is_err());
+    }
+}
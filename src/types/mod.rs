@@ -2,17 +2,22 @@
 mod byte_range;
 mod channels;
 mod closed_captions;
+mod codec;
 mod decimal_floating_point;
 mod decimal_resolution;
 mod decryption_key;
+mod encryption_coverage;
 mod encryption_method;
 mod hdcp_level;
 mod in_stream_id;
 mod initialization_vector;
 mod key_format;
 mod key_format_versions;
+mod lint;
 mod media_type;
 mod protocol_version;
+mod resolved_variant;
+mod segment_info;
 mod signed_decimal_floating_point;
 mod stream_inf;
 mod value;
@@ -20,17 +25,22 @@ mod value;
 pub use byte_range::*;
 pub use channels::*;
 pub use closed_captions::*;
+pub use codec::*;
 pub(crate) use decimal_floating_point::*;
 pub(crate) use decimal_resolution::*;
 pub use decryption_key::*;
+pub use encryption_coverage::*;
 pub use encryption_method::*;
 pub use hdcp_level::*;
 pub use in_stream_id::*;
 pub use initialization_vector::*;
 pub use key_format::*;
 pub use key_format_versions::*;
+pub use lint::*;
 pub use media_type::*;
 pub use protocol_version::*;
+pub use resolved_variant::*;
+pub use segment_info::*;
 pub(crate) use signed_decimal_floating_point::*;
 pub use stream_inf::*;
 pub use value::*;
@@ -22,6 +22,13 @@ pub enum ProtocolVersion {
 }
 
 impl ProtocolVersion {
+    /// The newest [`ProtocolVersion`], that is modelled by this library.
+    ///
+    /// Playlists, that declare a newer version, are parsed leniently, unless
+    /// the `reject_unsupported_version` option of the playlist builder is
+    /// enabled.
+    pub const MAX_SUPPORTED: Self = Self::V7;
+
     /// Returns the newest [`ProtocolVersion`], that is supported by
     /// this library.
     ///
@@ -30,7 +37,7 @@ impl ProtocolVersion {
     /// # use hls_m3u8::types::ProtocolVersion;
     /// assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V7);
     /// ```
-    pub const fn latest() -> Self { Self::V7 }
+    pub const fn latest() -> Self { Self::MAX_SUPPORTED }
 }
 
 impl fmt::Display for ProtocolVersion {
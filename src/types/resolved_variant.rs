@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::tags::ExtXStreamInf;
+use crate::MediaPlaylist;
+
+/// A variant stream of a [`MasterPlaylist`] together with the
+/// [`MediaPlaylist`], that has been fetched from its `URI`.
+///
+/// See [`ExtXStreamInf::with_media_playlist`].
+///
+/// [`MasterPlaylist`]: crate::MasterPlaylist
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedVariant {
+    pub(crate) stream_inf: ExtXStreamInf,
+    pub(crate) media_playlist: MediaPlaylist,
+}
+
+impl ResolvedVariant {
+    /// Returns the [`ExtXStreamInf`] tag of the variant stream.
+    pub const fn stream_inf(&self) -> &ExtXStreamInf { &self.stream_inf }
+
+    /// Returns the [`MediaPlaylist`] of the variant stream.
+    pub const fn media_playlist(&self) -> &MediaPlaylist { &self.media_playlist }
+
+    /// Returns the `URI` of the [`MediaPlaylist`].
+    pub const fn uri(&self) -> &String { self.stream_inf.uri() }
+
+    /// Returns the peak segment bit rate of the variant stream.
+    pub fn bandwidth(&self) -> u64 { self.stream_inf.bandwidth() }
+
+    /// Returns the number of [`MediaSegment`]s in the [`MediaPlaylist`].
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    pub const fn segment_count(&self) -> usize { self.media_playlist.segments().len() }
+
+    /// Returns the sum of the durations of all [`MediaSegment`]s in the
+    /// [`MediaPlaylist`].
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    pub fn duration(&self) -> Duration { self.media_playlist.durations().into_iter().sum() }
+
+    /// Splits the [`ResolvedVariant`] into the [`ExtXStreamInf`] tag and the
+    /// [`MediaPlaylist`].
+    pub fn into_parts(self) -> (ExtXStreamInf, MediaPlaylist) {
+        (self.stream_inf, self.media_playlist)
+    }
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use crate::MediaSegment;
+
+/// A [`MediaSegment`] together with its position in a [`MediaPlaylist`].
+///
+/// See [`MediaPlaylist::enumerate_segments`].
+///
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+/// [`MediaPlaylist::enumerate_segments`]:
+/// crate::MediaPlaylist::enumerate_segments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentInfo<'a> {
+    pub(crate) index: usize,
+    pub(crate) media_sequence: u64,
+    pub(crate) discontinuity_sequence: u64,
+    pub(crate) start: Duration,
+    pub(crate) segment: &'a MediaSegment,
+}
+
+impl<'a> SegmentInfo<'a> {
+    /// Returns the index of the segment in the playlist.
+    pub const fn index(&self) -> usize { self.index }
+
+    /// Returns the absolute media sequence number of the segment.
+    pub const fn media_sequence(&self) -> u64 { self.media_sequence }
+
+    /// Returns the discontinuity sequence number of the segment.
+    pub const fn discontinuity_sequence(&self) -> u64 { self.discontinuity_sequence }
+
+    /// Returns the sum of the durations of all preceding segments.
+    pub const fn start(&self) -> Duration { self.start }
+
+    /// Returns the segment.
+    pub const fn segment(&self) -> &'a MediaSegment { self.segment }
+}
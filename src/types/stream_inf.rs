@@ -4,7 +4,7 @@ use std::str::FromStr;
 use derive_builder::Builder;
 
 use crate::attribute::AttributePairs;
-use crate::types::{DecimalResolution, HdcpLevel};
+use crate::types::{Codec, DecimalResolution, HdcpLevel};
 use crate::utils::{quote, unquote};
 use crate::Error;
 
@@ -34,6 +34,14 @@ pub struct StreamInf {
     /// It indicates the set of video renditions, that should be used when
     /// playing the presentation.
     video: Option<String>,
+    #[builder(default)]
+    /// The content steering pathway, that the variant stream belongs to.
+    pathway_id: Option<String>,
+}
+
+impl StreamInfBuilder {
+    /// Returns `true`, if the bandwidth has been set.
+    pub(crate) const fn has_bandwidth(&self) -> bool { self.bandwidth.is_some() }
 }
 
 impl StreamInf {
@@ -53,6 +61,7 @@ impl StreamInf {
             resolution: None,
             hdcp_level: None,
             video: None,
+            pathway_id: None,
         }
     }
 
@@ -166,6 +175,31 @@ impl StreamInf {
         self
     }
 
+    /// Returns the codecs of the variant stream as a list of [`Codec`]s.
+    ///
+    /// Entries, that can not be parsed (e.g. an empty entry), are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use hls_m3u8::types::StreamInf;
+    /// #
+    /// let mut stream = StreamInf::new(20);
+    ///
+    /// stream.set_codecs(Some("mp4a.40.2,avc1.4d401e"));
+    ///
+    /// let codecs = stream.codec_list();
+    /// assert_eq!(codecs.len(), 2);
+    /// assert_eq!(codecs[0].family(), "mp4a");
+    /// assert_eq!(codecs[1].avc_profile_level(), Some((77, 30)));
+    /// ```
+    pub fn codec_list(&self) -> Vec<Codec> {
+        self.codecs
+            .iter()
+            .flat_map(|v| v.split(','))
+            .filter_map(|v| v.parse().ok())
+            .collect()
+    }
+
     /// Returns the resolution of the stream.
     ///
     /// # Examples
@@ -232,6 +266,35 @@ impl StreamInf {
         self.hdcp_level = value.map(Into::into);
         self
     }
+
+    /// Returns the identifier of the content steering pathway, that the
+    /// variant stream belongs to.
+    ///
+    /// # Examples
+    /// ```
+    /// # use hls_m3u8::types::StreamInf;
+    /// #
+    /// let stream = StreamInf::new(20);
+    /// assert_eq!(stream.pathway_id(), &None);
+    /// ```
+    pub const fn pathway_id(&self) -> &Option<String> { &self.pathway_id }
+
+    /// Sets the identifier of the content steering pathway, that the variant
+    /// stream belongs to.
+    ///
+    /// # Examples
+    /// ```
+    /// # use hls_m3u8::types::StreamInf;
+    /// #
+    /// let mut stream = StreamInf::new(20);
+    ///
+    /// stream.set_pathway_id(Some("CDN-A"));
+    /// assert_eq!(stream.pathway_id(), &Some("CDN-A".to_string()));
+    /// ```
+    pub fn set_pathway_id<T: ToString>(&mut self, value: Option<T>) -> &mut Self {
+        self.pathway_id = value.map(|v| v.to_string());
+        self
+    }
 }
 
 impl fmt::Display for StreamInf {
@@ -253,6 +316,9 @@ impl fmt::Display for StreamInf {
         if let Some(value) = &self.video {
             write!(f, ",VIDEO={}", quote(value))?;
         }
+        if let Some(value) = &self.pathway_id {
+            write!(f, ",PATHWAY-ID={}", quote(value))?;
+        }
         Ok(())
     }
 }
@@ -267,6 +333,7 @@ impl FromStr for StreamInf {
         let mut resolution = None;
         let mut hdcp_level = None;
         let mut video = None;
+        let mut pathway_id = None;
 
         for (key, value) in input.parse::<AttributePairs>()? {
             match key.as_str() {
@@ -276,6 +343,7 @@ impl FromStr for StreamInf {
                 "RESOLUTION" => resolution = Some(value.parse()?),
                 "HDCP-LEVEL" => hdcp_level = Some(value.parse()?),
                 "VIDEO" => video = Some(unquote(value)),
+                "PATHWAY-ID" => pathway_id = Some(unquote(value)),
                 _ => {
                     // [6.3.1. General Client Responsibilities]
                     // > ignore any attribute/value pair with an unrecognized
@@ -284,7 +352,7 @@ impl FromStr for StreamInf {
             }
         }
 
-        let bandwidth = bandwidth.ok_or_else(|| Error::missing_value("BANDWIDTH"))?;
+        let bandwidth = bandwidth.ok_or_else(|| Error::missing_attribute("BANDWIDTH"))?;
 
         Ok(Self {
             bandwidth,
@@ -293,6 +361,7 @@ impl FromStr for StreamInf {
             resolution,
             hdcp_level,
             video,
+            pathway_id,
         })
     }
 }
@@ -310,6 +379,7 @@ mod tests {
         stream_inf.set_resolution(1920, 1080);
         stream_inf.set_hdcp_level(Some(HdcpLevel::Type0));
         stream_inf.set_video(Some("video"));
+        stream_inf.set_pathway_id(Some("CDN-A"));
 
         assert_eq!(
             stream_inf.to_string(),
@@ -318,7 +388,8 @@ mod tests {
              CODECS=\"mp4a.40.2,avc1.4d401e\",\
              RESOLUTION=1920x1080,\
              HDCP-LEVEL=TYPE-0,\
-             VIDEO=\"video\""
+             VIDEO=\"video\",\
+             PATHWAY-ID=\"CDN-A\""
                 .to_string()
         );
     }
@@ -357,6 +428,14 @@ mod tests {
                 .unwrap()
         );
 
+        let mut stream_inf = StreamInf::new(200);
+        stream_inf.set_pathway_id(Some("CDN-A"));
+
+        assert_eq!(
+            stream_inf,
+            "BANDWIDTH=200,PATHWAY-ID=\"CDN-A\"".parse().unwrap()
+        );
+
         assert!("garbage".parse::<StreamInf>().is_err());
     }
 }
@@ -1,3 +1,6 @@
+use std::time::Duration;
+use std::{fmt, io};
+
 use crate::Error;
 
 macro_rules! required_version {
@@ -80,6 +83,33 @@ where
     Ok(result)
 }
 
+/// Rounds the duration to the nearest integer number of seconds.
+pub(crate) fn round_duration(duration: Duration) -> Duration {
+    if duration.subsec_nanos() < 500_000_000 {
+        Duration::from_secs(duration.as_secs())
+    } else {
+        Duration::from_secs(duration.as_secs() + 1)
+    }
+}
+
+/// Adapts an [`io::Write`] to [`fmt::Write`].
+///
+/// [`fmt::Write`] can only report the opaque [`fmt::Error`], so the first
+/// [`io::Error`] is kept in `error`, to be returned instead of it.
+pub(crate) struct IoWriter<'a, W> {
+    pub(crate) inner: &'a mut W,
+    pub(crate) error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write> fmt::Write for IoWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +127,11 @@ mod tests {
         assert_eq!(unquote("\"TestValue\""), "TestValue".to_string());
         assert_eq!(unquote("\"TestValue\n\""), "TestValue".to_string());
         assert_eq!(unquote("\"TestValue\n\r\""), "TestValue".to_string());
+        // percent-encoding is preserved:
+        assert_eq!(
+            unquote("\"https://example.com/a%20b.ts?c=d%2Ce;f\""),
+            "https://example.com/a%20b.ts?c=d%2Ce;f".to_string()
+        );
     }
 
     #[test]
@@ -105,6 +140,22 @@ mod tests {
         assert_eq!(quote("\"value\""), "\"value\"".to_string());
     }
 
+    #[test]
+    fn test_round_duration() {
+        assert_eq!(
+            round_duration(Duration::from_millis(9_499)),
+            Duration::from_secs(9)
+        );
+        assert_eq!(
+            round_duration(Duration::from_millis(9_500)),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            round_duration(Duration::from_secs(10)),
+            Duration::from_secs(10)
+        );
+    }
+
     #[test]
     fn test_tag() {
         let input = "HelloMyFriendThisIsASampleString";
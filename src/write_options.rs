@@ -0,0 +1,82 @@
+/// Options, that control how a playlist is written.
+///
+/// The [`Default`] options produce the same output as the [`Display`]
+/// implementation of the playlists.
+///
+/// # Example
+/// ```
+/// use hls_m3u8::WriteOptions;
+///
+/// let options = WriteOptions {
+///     final_newline: false,
+///     ..WriteOptions::default()
+/// };
+/// ```
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WriteOptions {
+    /// Whether the last line of the playlist is terminated by a `\n`.
+    ///
+    /// Defaults to `true`.
+    pub final_newline: bool,
+    /// Whether the `EXT-X-VERSION` tag is written, even if the playlist only
+    /// requires [`ProtocolVersion::V1`], which is the implied version, if
+    /// the tag is missing.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`ProtocolVersion::V1`]: crate::types::ProtocolVersion::V1
+    pub always_emit_version: bool,
+    /// The order, in which the attributes of the `EXT-X-MEDIA` tags of a
+    /// [`MasterPlaylist`] are written.
+    ///
+    /// Defaults to [`AttributeOrder::Default`].
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    pub media_attribute_order: AttributeOrder,
+    /// Whether the tags of a parsed [`MasterPlaylist`] are written in the
+    /// order, in which they appeared in the document, instead of being
+    /// grouped by tag.
+    ///
+    /// Tags, that have been added after parsing, are written after the
+    /// recorded ones in the default order.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    pub preserve_document_order: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            final_newline: true,
+            always_emit_version: false,
+            media_attribute_order: AttributeOrder::default(),
+            preserve_document_order: false,
+        }
+    }
+}
+
+/// The order, in which the attributes of a tag are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeOrder {
+    /// The order of the [`Display`] implementation of the tags.
+    ///
+    /// For example `TYPE`, `URI`, `GROUP-ID`, `LANGUAGE`, ..., `NAME`, ...
+    /// for an `EXT-X-MEDIA` tag.
+    ///
+    /// [`Display`]: std::fmt::Display
+    Default,
+    /// The order of the example playlists of Apple, which allows a bit-level
+    /// comparison with them.
+    ///
+    /// For example `TYPE`, `GROUP-ID`, `NAME`, `LANGUAGE`, ..., `URI` for an
+    /// `EXT-X-MEDIA` tag.
+    AppleCompatible,
+}
+
+impl Default for AttributeOrder {
+    fn default() -> Self { Self::Default }
+}